@@ -0,0 +1,261 @@
+// Copyright 2023-2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Converts JSON ABIs into Solidity interfaces.
+
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Converts a JSON ABI into the source of an equivalent Solidity interface.
+///
+/// Both a bare ABI array and a compiler artifact with an `abi` field are accepted.
+pub fn interface_source(name: &str, json: &str) -> Result<String, String> {
+    let json: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let abi = match &json {
+        Value::Object(artifact) => artifact.get("abi").unwrap_or(&Value::Null),
+        abi => abi,
+    };
+    let Value::Array(items) = abi else {
+        return Err("expected an ABI array".into());
+    };
+
+    let mut structs = vec![];
+    let mut body = String::new();
+    for item in items {
+        // the type may be omitted, in which case it defaults to a function
+        let kind = item
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("function");
+        let name = item.get("name").and_then(Value::as_str).unwrap_or_default();
+        let inputs = params(item, "inputs", &mut structs)?;
+        match kind {
+            "function" => {
+                let mutability = match state_mutability(item) {
+                    "nonpayable" => "",
+                    "pure" => " pure",
+                    "view" => " view",
+                    "payable" => " payable",
+                    other => return Err(format!("unknown state mutability {other}")),
+                };
+                let outputs = params(item, "outputs", &mut structs)?;
+                let returns = match outputs.is_empty() {
+                    true => String::new(),
+                    false => format!(" returns ({outputs})"),
+                };
+                writeln!(
+                    body,
+                    "function {name}({inputs}) external{mutability}{returns};"
+                )
+                .unwrap();
+            }
+            "event" => {
+                let anonymous = match item.get("anonymous").and_then(Value::as_bool) {
+                    Some(true) => " anonymous",
+                    _ => "",
+                };
+                writeln!(body, "event {name}({inputs}){anonymous};").unwrap();
+            }
+            "error" => writeln!(body, "error {name}({inputs});").unwrap(),
+            _ => {} // constructors, fallbacks, and receives aren't callable by name
+        }
+    }
+
+    let mut source = format!("interface {name} {{\n");
+    for (name, fields) in structs {
+        writeln!(source, "struct {name} {{ {fields} }}").unwrap();
+    }
+    source += &body;
+    source += "}";
+    Ok(source)
+}
+
+/// Formats a list of parameters, collecting any structs they use.
+fn params(item: &Value, key: &str, structs: &mut Vec<(String, String)>) -> Result<String, String> {
+    let Some(params) = item.get(key).and_then(Value::as_array) else {
+        return Ok(String::new());
+    };
+    let mut out = vec![];
+    for param in params {
+        let mut text = param_type(param, structs)?;
+        if let Some(true) = param.get("indexed").and_then(Value::as_bool) {
+            text += " indexed";
+        }
+        match param.get("name").and_then(Value::as_str) {
+            Some(name) if !name.is_empty() => write!(text, " {name}").unwrap(),
+            _ => {}
+        }
+        out.push(text);
+    }
+    Ok(out.join(", "))
+}
+
+/// Determines the Solidity type of a parameter, declaring a struct for each named tuple.
+fn param_type(param: &Value, structs: &mut Vec<(String, String)>) -> Result<String, String> {
+    let ty = field(param, "type")?;
+    let Some(suffix) = ty.strip_prefix("tuple") else {
+        return Ok(ty.to_string());
+    };
+
+    let components = param
+        .get("components")
+        .and_then(Value::as_array)
+        .ok_or("tuple without components")?;
+    let mut types = vec![];
+    for component in components {
+        types.push(param_type(component, structs)?);
+    }
+
+    // internal types look like `struct IService.Payment[]`
+    let internal = param.get("internalType").and_then(Value::as_str);
+    let path = internal
+        .and_then(|ty| ty.strip_prefix("struct "))
+        .and_then(|ty| ty.split('[').next());
+    let Some(path) = path else {
+        return Ok(format!("({}){suffix}", types.join(", ")));
+    };
+
+    let mut fields = String::new();
+    for (i, (component, ty)) in components.iter().zip(types).enumerate() {
+        let field = component.get("name").and_then(Value::as_str);
+        let field = field.filter(|f| !f.is_empty());
+        match field {
+            Some(field) => write!(fields, "{ty} {field}; ").unwrap(),
+            None => write!(fields, "{ty} field{i}; ").unwrap(),
+        }
+    }
+    let fields = fields.trim_end().to_string();
+
+    // structs from different contracts may share a name, in which case later ones are qualified
+    let short = path.rsplit('.').next().unwrap_or(path);
+    let qualified = path.replace('.', "_");
+    for name in [short, &qualified] {
+        match structs.iter().find(|(other, _)| other == name) {
+            Some((_, other)) if *other == fields => return Ok(format!("{name}{suffix}")),
+            Some(_) => continue,
+            None => {
+                structs.push((name.to_string(), fields));
+                return Ok(format!("{name}{suffix}"));
+            }
+        }
+    }
+    Err(format!("conflicting definitions of struct {path}"))
+}
+
+/// Gets a function's state mutability, which older compilers give via `constant` and `payable`.
+fn state_mutability(item: &Value) -> &str {
+    if let Some(mutability) = item.get("stateMutability").and_then(Value::as_str) {
+        return mutability;
+    }
+    let flag = |key| item.get(key).and_then(Value::as_bool).unwrap_or_default();
+    match (flag("constant"), flag("payable")) {
+        (true, _) => "view",
+        (_, true) => "payable",
+        _ => "nonpayable",
+    }
+}
+
+/// Gets a required string field.
+fn field<'a>(value: &'a Value, key: &str) -> Result<&'a str, String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("missing \"{key}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interface_source;
+
+    #[test]
+    fn test_interface_source() {
+        let abi = r#"[
+            {"type": "constructor", "inputs": [], "stateMutability": "nonpayable"},
+            {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "value", "type": "uint256"}],
+             "outputs": [{"name": "", "type": "bool"}]},
+            {"type": "function", "name": "points", "stateMutability": "view", "inputs": [],
+             "outputs": [{"name": "", "type": "tuple[]", "internalType": "struct IShape.Point[]",
+                          "components": [{"name": "x", "type": "uint64"}, {"name": "y", "type": "uint64"}]}]},
+            {"type": "event", "name": "Transfer", "anonymous": false,
+             "inputs": [{"name": "from", "type": "address", "indexed": true},
+                        {"name": "value", "type": "uint256", "indexed": false}]},
+            {"type": "error", "name": "Denied", "inputs": [{"name": "", "type": "address"}]}
+        ]"#;
+        let expected = "interface IShape {
+struct Point { uint64 x; uint64 y; }
+function transfer(address to, uint256 value) external returns (bool);
+function points() external view returns (Point[]);
+event Transfer(address indexed from, uint256 value);
+error Denied(address);
+}";
+        assert_eq!(interface_source("IShape", abi).unwrap(), expected);
+
+        let artifact = format!(r#"{{"abi": {abi}, "bytecode": "0x"}}"#);
+        assert_eq!(interface_source("IShape", &artifact).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_default_type() {
+        let abi = r#"[{"name": "owner", "stateMutability": "view", "inputs": [],
+                       "outputs": [{"name": "", "type": "address"}]}]"#;
+        let expected = "interface IOwned {
+function owner() external view returns (address);
+}";
+        assert_eq!(interface_source("IOwned", abi).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_struct_names() {
+        let abi = r#"[
+            {"type": "function", "name": "a", "stateMutability": "view", "outputs": [],
+             "inputs": [{"name": "info", "type": "tuple", "internalType": "struct A.Info",
+                         "components": [{"name": "value", "type": "uint256"}]}]},
+            {"type": "function", "name": "b", "stateMutability": "view", "outputs": [],
+             "inputs": [{"name": "info", "type": "tuple[]", "internalType": "struct B.Info[]",
+                         "components": [{"name": "owner", "type": "address"}]}]},
+            {"type": "function", "name": "c", "stateMutability": "view", "outputs": [],
+             "inputs": [{"name": "info", "type": "tuple", "internalType": "struct A.Info",
+                         "components": [{"name": "value", "type": "uint256"}]}]}
+        ]"#;
+        let expected = "interface IInfo {
+struct Info { uint256 value; }
+struct B_Info { address owner; }
+function a(Info info) external view;
+function b(B_Info[] info) external view;
+function c(Info info) external view;
+}";
+        assert_eq!(interface_source("IInfo", abi).unwrap(), expected);
+
+        let abi = r#"[
+            {"type": "function", "name": "a", "stateMutability": "view", "outputs": [],
+             "inputs": [{"name": "info", "type": "tuple", "internalType": "struct Info",
+                         "components": [{"name": "value", "type": "uint256"}]}]},
+            {"type": "function", "name": "b", "stateMutability": "view", "outputs": [],
+             "inputs": [{"name": "info", "type": "tuple", "internalType": "struct Info",
+                         "components": [{"name": "owner", "type": "address"}]}]}
+        ]"#;
+        let err = interface_source("IInfo", abi).unwrap_err();
+        assert_eq!(err, "conflicting definitions of struct Info");
+    }
+
+    #[test]
+    fn test_legacy_mutability() {
+        let abi = r#"[
+            {"constant": true, "name": "totalSupply", "inputs": [],
+             "outputs": [{"name": "", "type": "uint256"}], "payable": false, "type": "function"},
+            {"constant": false, "name": "deposit", "inputs": [], "outputs": [],
+             "payable": true, "type": "function"},
+            {"constant": false, "name": "withdraw", "inputs": [{"name": "wad", "type": "uint256"}],
+             "outputs": [], "payable": false, "type": "function"},
+            {"name": "approve", "inputs": [], "outputs": []}
+        ]"#;
+        let expected = "interface IWETH {
+function totalSupply() external view returns (uint256);
+function deposit() external payable;
+function withdraw(uint256 wad) external;
+function approve() external;
+}";
+        assert_eq!(interface_source("IWETH", abi).unwrap(), expected);
+    }
+}
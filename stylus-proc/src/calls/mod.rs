@@ -1,16 +1,43 @@
 // Copyright 2023-2024, Offchain Labs, Inc.
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
-use crate::types::solidity_type_info;
+use crate::types::{solidity_type_info, CustomTypes};
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
-use quote::quote;
+use quote::{format_ident, quote, ToTokens};
 use sha3::{Digest, Keccak256};
-use std::borrow::Cow;
-use syn_solidity::{FunctionAttribute, Item, Mutability, SolIdent, Spanned, Visibility};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    path::Path,
+    str::FromStr,
+};
+use syn::{
+    parse::{Parse, ParseStream},
+    LitStr, Token,
+};
+use syn_solidity::{
+    FunctionAttribute, Item, ItemFunction, Mutability, SolIdent, Spanned, Visibility,
+};
+
+/// The items of an interface, including those it inherits.
+struct Interface {
+    functions: Vec<ItemFunction>,
+    /// The events, errors, structs, and enums.
+    types: Vec<Item>,
+}
+
+mod json;
 
 pub fn sol_interface(input: TokenStream) -> TokenStream {
+    if let Ok(args) = syn::parse::<JsonArgs>(input.clone()) {
+        return json_interface(args);
+    }
+
+    let tokens = proc_macro2::TokenStream::from(input.clone());
     let input = match syn_solidity::parse(input) {
         Ok(f) => f,
         Err(err) => return err.to_compile_error().into(),
@@ -23,10 +50,25 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
     let sol_value = quote!(stylus_sdk::alloy_sol_types::SolValue);
     let sol_type_value = quote!(stylus_sdk::alloy_sol_types::private::SolTypeValue);
 
+    macro_rules! check {
+        ($result:expr) => {
+            match $result {
+                Ok(value) => value,
+                Err(err) => return err.to_compile_error().into(),
+            }
+        };
+    }
+
     let mut output = quote!();
 
+    // the items of each interface seen so far
+    let mut interfaces: HashMap<String, Interface> = HashMap::new();
+
     for item in input.items {
         let mut method_impls = quote!();
+        let mut function_infos = quote!();
+        let mut return_structs = vec![];
+        let mut return_names = vec![];
 
         let Item::Contract(contract) = item else {
             error!(item.span(), "not an interface")
@@ -34,21 +76,94 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
         if !contract.is_interface() {
             error!(contract.kind.span(), "not an interface");
         }
-        if let Some(inherits) = contract.inheritance {
-            error!(inherits.span(), "inheritance is not currently supported");
-        }
 
         let name = &contract.name;
 
+        let mut functions = vec![];
+        let mut types = vec![];
         for item in contract.body {
-            let Item::Function(func) = item else {
-                error!(item.span(), "unsupported interface item");
-            };
+            match item {
+                Item::Function(func) => functions.push(func),
+                Item::Event(_) | Item::Error(_) | Item::Struct(_) | Item::Enum(_) => {
+                    types.push(item)
+                }
+                _ => error!(item.span(), "unsupported interface item"),
+            }
+        }
+
+        // collect inherited items, which the child may redeclare
+        let mut inherited = vec![];
+        if let Some(inherits) = &contract.inheritance {
+            for parent in &inherits.inheritance {
+                if parent.paren_token.is_some() {
+                    error!(parent.name.span(), "interfaces do not take arguments");
+                }
+                if parent.name.len() != 1 {
+                    error!(
+                        parent.name.span(),
+                        "inherited interfaces must be named directly"
+                    );
+                }
+                let parent = parent.name.first();
+
+                let Some(parent) = interfaces.get(&parent.as_string()) else {
+                    // defined by an earlier invocation, so expand its definition ahead of ours
+                    let parent = Ident::from(parent.clone());
+                    return quote! {
+                        #parent! { #tokens }
+                    }
+                    .into();
+                };
+                for item in &parent.types {
+                    if !types.iter().any(|t| t.name() == item.name()) {
+                        types.push(item.clone());
+                    }
+                }
+                inherited.extend(parent.functions.iter().cloned());
+            }
+        }
+
+        // structs and enums are generated under a module named after the interface
+        let namespace = Ident::new(&name.as_string().to_case(Case::Snake), name.span());
+        let custom = check!(custom_types(&namespace, &types));
+
+        // inherited functions come first, in the order of the parents
+        let mut signatures = vec![];
+        for func in &functions {
+            signatures.push(check!(function_signature(func, &custom)));
+        }
+        let mut parent_functions = vec![];
+        for func in inherited {
+            let signature = check!(function_signature(&func, &custom));
+            if !signatures.contains(&signature) {
+                signatures.push(signature);
+                parent_functions.push(func);
+            }
+        }
+        parent_functions.append(&mut functions);
+        let functions = parent_functions;
+        let interface = Interface {
+            functions: functions.clone(),
+            types: types.clone(),
+        };
+        interfaces.insert(name.as_string(), interface);
+
+        // skip interfaces defined elsewhere and included only for their items
+        if contract
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("inherited"))
+        {
+            continue;
+        }
+
+        let rust_names = check!(rust_names(&functions));
+        for (func, rust_name) in functions.iter().zip(rust_names) {
             // uncomment when Alloy exposes this enum
             //     if let FunctionKind::Function(_) = func.kind {
             //         continue;
             //     }
-            let Some(name) = &func.name else {
+            let Some(rust_name) = rust_name else {
                 continue;
             };
 
@@ -90,18 +205,21 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
             let purity = purity.unwrap_or(Write);
 
             // determine which context and kind of call to use
-            let (context, call) = match purity {
+            let (context, call, try_call) = match purity {
                 Pure | View => (
                     quote! { impl stylus_sdk::call::StaticCallContext },
                     quote! { stylus_sdk::call::static_call },
+                    quote! { stylus_sdk::call::try_static_call },
                 ),
                 Write => (
                     quote! { impl stylus_sdk::call::NonPayableCallContext },
                     quote! { stylus_sdk::call::call },
+                    quote! { stylus_sdk::call::try_call },
                 ),
                 Payable => (
                     quote! { impl stylus_sdk::call::MutatingCallContext },
                     quote! { stylus_sdk::call::call },
+                    quote! { stylus_sdk::call::try_call },
                 ),
             };
 
@@ -115,25 +233,53 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
             }
 
             // get the return type
-            let return_type = match func.return_type() {
-                Some(ty) => solidity_type_info(&ty).0,
-                None => Cow::from("()"),
+            let (return_type, return_abi) = match func.return_type() {
+                Some(ty) => check!(solidity_type_info(&ty, &custom)),
+                None => (Cow::from("()"), Cow::from("()")),
             };
-            let return_type: syn::Type = parse!(&return_type);
+            let mut return_type: syn::Type = parse!(&return_type);
+            let mut decode_return = quote! {
+                Ok(<(#return_type,) as #sol_type>::abi_decode_params(data, true)?.0)
+            };
+
+            // named return values are gathered into a struct
+            let returns = func.returns.iter().flat_map(|r| r.returns.iter());
+            let fields: Vec<_> = returns.clone().filter_map(|r| r.name.as_ref()).collect();
+            if fields.len() > 1 && fields.len() == returns.clone().count() {
+                let pascal = rust_name.to_string().to_case(Case::Pascal);
+                let struct_name = format_ident!("{pascal}Return");
+                let taken = types.iter().any(|item| match item.name() {
+                    Some(name) => struct_name == name.as_string(),
+                    None => false,
+                });
+                if taken || return_names.contains(&struct_name) {
+                    error!(
+                        func.name.as_ref().unwrap(),
+                        "`{struct_name}` collides with another type, consider `#[rust_name]`"
+                    );
+                }
+                return_names.push(struct_name.clone());
+                let fields = returns.map(|r| {
+                    let ty = proc_macro2::TokenStream::from_str(&r.ty.to_string()).unwrap();
+                    let name = r.name.as_ref().unwrap();
+                    quote! { #ty #name; }
+                });
+                return_structs.push(quote! {
+                    struct #struct_name { #(#fields)* }
+                });
+                return_type = parse!(format!("{namespace}::{struct_name}"));
+                decode_return = quote! {
+                    <#return_type as #sol_type>::abi_decode_params(data, true)
+                };
+            }
 
-            let mut selector = Keccak256::new();
-            selector.update(name.to_string());
-            selector.update("(");
+            let signature = check!(function_signature(func, &custom));
+            let selector = Keccak256::digest(&signature);
             let mut sol_args = vec![];
             let mut rust_args = vec![];
             let mut rust_arg_names = vec![];
             for (i, arg) in func.parameters.iter().enumerate() {
-                let (sol_path, abi) = solidity_type_info(&arg.ty);
-                if i > 0 {
-                    selector.update(",");
-                }
-                selector.update(&*abi);
-
+                let (sol_path, _) = check!(solidity_type_info(&arg.ty, &custom));
                 let ty: syn::Type = parse!(&sol_path);
                 let name = arg
                     .name
@@ -147,30 +293,115 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
                 sol_args.push(ty);
                 rust_arg_names.push(name);
             }
-            selector.update(")");
-
-            let selector = selector.finalize();
             let selector0 = selector[0];
             let selector1 = selector[1];
             let selector2 = selector[2];
             let selector3 = selector[3];
 
-            let rust_name = Ident::new(&name.to_string().to_case(Case::Snake), name.span());
+            let selector_u32 = u32::from_be_bytes([selector0, selector1, selector2, selector3]);
+            let sdk_purity = purity.as_tokens();
+            function_infos.extend(quote! {
+                stylus_sdk::abi::InterfaceFunction {
+                    signature: #signature,
+                    selector: #selector_u32,
+                    purity: #sdk_purity,
+                    returns: #return_abi,
+                },
+            });
 
+            let try_name = format_ident!("try_{}", rust_name);
+            let encode = format_ident!("encode_{}", rust_name);
+            let decode = format_ident!("decode_{}_return", rust_name);
+            let prepare = format_ident!("prepare_{}", rust_name);
             method_impls.extend(quote! {
                 pub fn #rust_name(&self, context: #context #(, #rust_args)*) ->
                     Result<<#return_type as #sol_type>::RustType, stylus_sdk::call::Error>
                 {
+                    let calldata = Self::#encode(#(#rust_arg_names,)*);
+                    let returned = #call(context, self.address, &calldata)?;
+                    Ok(Self::#decode(&returned)?)
+                }
+
+                pub fn #try_name(&self, context: #context #(, #rust_args)*) ->
+                    stylus_sdk::call::CallOutcome<<#return_type as #sol_type>::RustType>
+                {
+                    let calldata = Self::#encode(#(#rust_arg_names,)*);
+                    #try_call(context, self.address, &calldata).decode(Self::#decode)
+                }
+
+                pub fn #encode(#(#rust_args),*) -> alloc::vec::Vec<u8> {
                     use alloc::vec;
                     let args = <(#(#sol_args,)*) as #sol_type>::abi_encode_params(&(#(#rust_arg_names,)*));
                     let mut calldata = vec![#selector0, #selector1, #selector2, #selector3];
                     calldata.extend(args);
-                    let returned = #call(context, self.address, &calldata)?;
-                    Ok(<(#return_type,) as #sol_type>::abi_decode_params(&returned, true)?.0)
+                    calldata
+                }
+
+                pub fn #decode(data: &[u8]) ->
+                    Result<<#return_type as #sol_type>::RustType, stylus_sdk::alloy_sol_types::Error>
+                {
+                    #decode_return
+                }
+
+                pub fn #prepare(&self #(, #rust_args)*) ->
+                    stylus_sdk::call::PendingCall<<#return_type as #sol_type>::RustType>
+                {
+                    stylus_sdk::call::PendingCall {
+                        to: self.address,
+                        calldata: Self::#encode(#(#rust_arg_names,)*),
+                        decode: Self::#decode,
+                    }
+                }
+            });
+        }
+
+        // allows interfaces in later invocations to inherit this one
+        let module = format_ident!("__stylus_{}", name.as_string());
+        let type_definitions: Vec<_> = types
+            .iter()
+            .map(|item| item_tokens(item.attrs().unwrap(), item))
+            .collect();
+        let definition = functions.iter().map(|func| item_tokens(&func.attrs, func));
+        output.extend(quote! {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            mod #module {
+                #[allow(unused_macros)]
+                macro_rules! #name {
+                    ($($input:tt)*) => {
+                        stylus_sdk::prelude::sol_interface! {
+                            #[inherited]
+                            interface #name {
+                                #(#type_definitions)*
+                                #(#definition)*
+                            }
+                            $($input)*
+                        }
+                    };
+                }
+                #[allow(unused_imports)]
+                pub(crate) use #name;
+            }
+            #[allow(unused_imports)]
+            pub(crate) use #module::#name;
+        });
+
+        if !types.is_empty() || !return_structs.is_empty() {
+            output.extend(quote! {
+                pub mod #namespace {
+                    stylus_sdk::alloy_sol_types::sol! {
+                        #![sol(alloy_sol_types = stylus_sdk::alloy_sol_types)]
+                        interface #name {
+                            #(#type_definitions)*
+                            #(#return_structs)*
+                        }
+                    }
+                    pub use self::#name::*;
                 }
             });
         }
 
+        let interface_name = name.as_string();
         output.extend(quote! {
             pub struct #name {
                 pub address: #alloy_address,
@@ -184,6 +415,12 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
                 #method_impls
             }
 
+            impl From<#alloy_address> for #name {
+                fn from(address: #alloy_address) -> Self {
+                    Self::new(address)
+                }
+            }
+
             impl core::ops::Deref for #name {
                 type Target = #alloy_address;
 
@@ -241,7 +478,264 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
 
                 const ABI: stylus_sdk::abi::ConstString = <#alloy_address as stylus_sdk::abi::AbiType>::ABI;
             }
+
+            impl stylus_sdk::abi::Interface for #name {
+                const NAME: &'static str = #interface_name;
+
+                const FUNCTIONS: &'static [stylus_sdk::abi::InterfaceFunction] = &[#function_infos];
+            }
         });
     }
     output.into()
 }
+
+/// Arguments for defining an interface from a JSON ABI, as in `sol_interface!(IERC20, "IERC20.json")`.
+struct JsonArgs {
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for JsonArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let path = input.parse()?;
+        let _: Option<Token![,]> = input.parse()?;
+        Ok(Self { name, path })
+    }
+}
+
+/// Defines an interface from a JSON ABI file relative to the crate's manifest.
+fn json_interface(args: JsonArgs) -> TokenStream {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&dir).join(args.path.value());
+    let json = match fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(err) => error!(args.path, "failed to read {}: {err}", path.display()),
+    };
+    let source = match json::interface_source(&args.name.to_string(), &json) {
+        Ok(source) => source,
+        Err(err) => error!(args.path, "invalid JSON ABI: {err}"),
+    };
+    let source = match proc_macro2::TokenStream::from_str(&source) {
+        Ok(source) => source,
+        Err(err) => error!(args.path, "invalid JSON ABI: {err}"),
+    };
+
+    // recompile when the file changes
+    let path = path.to_string_lossy();
+    let interface = proc_macro2::TokenStream::from(sol_interface(source.into()));
+    quote! {
+        const _: &[u8] = include_bytes!(#path);
+        #interface
+    }
+    .into()
+}
+
+/// Returns the Solidity signature of a function, which uniquely identifies it within an interface.
+fn function_signature(func: &ItemFunction, custom: &CustomTypes) -> syn::Result<String> {
+    let name = func
+        .name
+        .as_ref()
+        .map(SolIdent::as_string)
+        .unwrap_or_default();
+    let mut args = vec![];
+    for arg in &func.parameters {
+        args.push(solidity_type_info(&arg.ty, custom)?.1);
+    }
+    Ok(format!("{name}({})", args.join(",")))
+}
+
+/// Chooses the Rust name of each function.
+///
+/// Functions may be renamed via `#[rust_name = "..."]`. Any remaining overloads are suffixed with
+/// their index among one another, in declaration order. Errors if any of the methods generated for
+/// a function would collide with another's.
+fn rust_names(functions: &[ItemFunction]) -> syn::Result<Vec<Option<Ident>>> {
+    let mut renames = vec![];
+    let mut overloads: HashMap<String, usize> = HashMap::new();
+    for func in functions {
+        let mut rename = None;
+        for attr in &func.attrs {
+            if !attr.path().is_ident("rust_name") {
+                continue;
+            }
+            let meta: syn::MetaNameValue = syn::parse2(attr.meta.to_token_stream())?;
+            let syn::Lit::Str(lit) = meta.lit else {
+                error!(@meta.lit, "expected a string");
+            };
+            rename = Some(lit.parse::<Ident>()?);
+        }
+        if let (Some(name), None) = (&func.name, &rename) {
+            *overloads.entry(name.as_string()).or_default() += 1;
+        }
+        renames.push(rename);
+    }
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut methods = HashSet::from(["new".to_string()]);
+    let mut names: Vec<Option<Ident>> = vec![];
+    for (func, rename) in functions.iter().zip(renames) {
+        let Some(name) = &func.name else {
+            names.push(None);
+            continue;
+        };
+        let rust_name = match rename {
+            Some(rename) => rename,
+            None => {
+                let index = seen.entry(name.as_string()).or_default();
+                let rust_name = name.as_string().to_case(Case::Snake);
+                let rust_name = match overloads[&name.as_string()] {
+                    1 => Ident::new(&rust_name, name.span()),
+                    _ => Ident::new(&format!("{rust_name}_{index}"), name.span()),
+                };
+                *index += 1;
+                rust_name
+            }
+        };
+
+        // each function generates several methods, none of which may collide with another's
+        let generated = [
+            rust_name.to_string(),
+            format!("try_{rust_name}"),
+            format!("encode_{rust_name}"),
+            format!("decode_{rust_name}_return"),
+            format!("prepare_{rust_name}"),
+        ];
+        for method in generated {
+            if !methods.insert(method.clone()) {
+                error!(@name.span(), "duplicate method `{method}`, consider `#[rust_name]`");
+            }
+        }
+        names.push(Some(rust_name));
+    }
+    Ok(names)
+}
+
+/// Resolves the alloy path and ABI of the structs and enums generated under `namespace`.
+fn custom_types(namespace: &Ident, types: &[Item]) -> syn::Result<CustomTypes> {
+    let mut custom = CustomTypes::new();
+    let mut structs = vec![];
+    for item in types {
+        match item {
+            Item::Enum(item) => {
+                let path = format!("{namespace}::{}", item.name);
+                custom.insert(item.name.as_string(), (path, "uint8".into()));
+            }
+            Item::Struct(item) => structs.push(item),
+            _ => {}
+        }
+    }
+
+    // structs may contain one another, so resolve them in dependency order
+    while !structs.is_empty() {
+        let pending = structs.len();
+        let mut error = None;
+        structs.retain(|item| {
+            let mut fields = vec![];
+            for field in item.fields.iter() {
+                match solidity_type_info(&field.ty, &custom) {
+                    Ok((_, abi)) => fields.push(abi),
+                    Err(err) => {
+                        error.get_or_insert(err);
+                        return true;
+                    }
+                }
+            }
+            let path = format!("{namespace}::{}", item.name);
+            let abi = format!("({})", fields.join(","));
+            custom.insert(item.name.as_string(), (path, abi));
+            false
+        });
+        if structs.len() == pending {
+            return Err(error.unwrap());
+        }
+    }
+    Ok(custom)
+}
+
+/// Re-creates the tokens of an item, since displaying it drops its attributes.
+fn item_tokens(attrs: &[impl ToTokens], item: &impl Display) -> proc_macro2::TokenStream {
+    let item = proc_macro2::TokenStream::from_str(&item.to_string()).unwrap();
+    quote! { #(#attrs)* #item }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rust_names;
+    use quote::quote;
+    use syn_solidity::{Item, ItemFunction};
+
+    fn functions(tokens: proc_macro2::TokenStream) -> Vec<ItemFunction> {
+        let file = syn_solidity::parse2(tokens).unwrap();
+        let Item::Contract(contract) = &file.items[0] else {
+            panic!("not an interface");
+        };
+        let functions = contract.body.iter().filter_map(|item| match item {
+            Item::Function(func) => Some(func.clone()),
+            _ => None,
+        });
+        functions.collect()
+    }
+
+    fn names(tokens: proc_macro2::TokenStream) -> syn::Result<Vec<String>> {
+        let names = rust_names(&functions(tokens))?;
+        Ok(names.into_iter().flatten().map(|n| n.to_string()).collect())
+    }
+
+    #[test]
+    fn test_rust_names() {
+        let names = names(quote! {
+            interface IERC721 {
+                function safeTransferFrom(address from, address to, uint256 id) external;
+                #[rust_name = "safe_transfer_from_with_data"]
+                function safeTransferFrom(address from, address to, uint256 id, bytes data) external;
+                function mint(address to) external;
+                function mint(address to, uint256 amount) external;
+                function balanceOf(address owner) external view returns (uint256);
+            }
+        });
+        let expected = [
+            "safe_transfer_from",
+            "safe_transfer_from_with_data",
+            "mint_0",
+            "mint_1",
+            "balance_of",
+        ];
+        assert_eq!(names.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rust_name_collisions() {
+        let overload = names(quote! {
+            interface IToken {
+                function mint(address to) external;
+                function mint(address to, uint256 amount) external;
+                function mint_1() external;
+            }
+        });
+        let err = overload.unwrap_err().to_string();
+        assert_eq!(err, "duplicate method `mint_1`, consider `#[rust_name]`");
+
+        let prefixed = names(quote! {
+            interface IToken {
+                function transfer(address to) external;
+                function tryTransfer(address to) external;
+            }
+        });
+        let err = prefixed.unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "duplicate method `try_transfer`, consider `#[rust_name]`"
+        );
+
+        let renamed = names(quote! {
+            interface IToken {
+                function burn(uint256 amount) external;
+                #[rust_name = "burn"]
+                function destroy(uint256 amount) external;
+            }
+        });
+        assert!(renamed.is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Fields, ItemStruct};
+
+pub fn derive_eip712(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemStruct);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Fields::Named(fields) = &input.fields else {
+        error!(input.fields, "EIP-712 structs must have named fields");
+    };
+
+    let mut root_type = quote!();
+    let mut components = quote!();
+    let mut encode_data = quote!();
+    for (i, field) in fields.named.iter().enumerate() {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let comma = if i > 0 { "," } else { "" };
+        let member = format!(" {}", ident.to_string().to_case(Case::Camel));
+
+        root_type.extend(quote! {
+            root += #comma;
+            root += &<#ty as Eip712Type>::type_name();
+            root += #member;
+        });
+        components.extend(quote! {
+            <#ty as Eip712Type>::components(out);
+        });
+        encode_data.extend(quote! {
+            hasher.update(Eip712Type::encode_value(&self.#ident));
+        });
+    }
+
+    let name_str = name.to_string();
+    quote! {
+        impl #impl_generics stylus_sdk::crypto::eip712::Eip712 for #name #ty_generics #where_clause {
+            const NAME: &'static str = #name_str;
+
+            #[allow(unused)]
+            fn root_type() -> alloc::string::String {
+                use stylus_sdk::crypto::eip712::Eip712Type;
+                let mut root = alloc::string::String::from(#name_str);
+                root += "(";
+                #root_type
+                root += ")";
+                root
+            }
+
+            #[allow(unused)]
+            fn components(out: &mut alloc::vec::Vec<alloc::string::String>) {
+                use stylus_sdk::crypto::eip712::Eip712Type;
+                #components
+            }
+
+            #[allow(unused)]
+            fn encode_data(&self, hasher: &mut stylus_sdk::crypto::Keccak) {
+                use stylus_sdk::crypto::eip712::Eip712Type;
+                #encode_data
+            }
+        }
+    }
+    .into()
+}
@@ -31,6 +31,7 @@ macro_rules! error {
 }
 
 mod calls;
+mod eip712;
 mod methods;
 mod storage;
 mod types;
@@ -57,10 +58,35 @@ mod types;
 /// One may even implement [`StorageType`] to define custom storage entries, though this is rarely necessary
 /// since the [Stylus SDK][sdk] intends to include all standard Solidity types out-of-the-box.
 ///
+/// # Namespaced storage
+///
+/// By default, fields are laid out sequentially from the struct's root, which for an
+/// [`#[entrypoint]`][entrypoint] is slot zero. Upgradeable and modular contracts can instead root
+/// a struct at an [`ERC-7201`] namespace, computed at compile time via [`erc7201_slot`].
+///
+/// ```ignore
+/// #[storage(namespace = "myproject.erc20")]
+/// pub struct Erc20 {
+///     balances: StorageMap<Address, StorageU256>,
+///     total_supply: StorageU256,
+/// }
+/// ```
+///
+/// Namespaced structs live at their namespace's root whether they're the
+/// [`#[entrypoint]`][entrypoint] or embedded in another struct, in which case they take up no space
+/// in the parent. Since every instance shares the same slots, they shouldn't be stored in
+/// collections like `StorageVec`.
+///
+/// When exporting the ABI, the interfaces of namespaced structs are annotated with
+/// `@custom:storage-location erc7201:<namespace>`.
+///
 /// Please refer to the [SDK Feature Overview][overview] for more information on defining storage.
 ///
 /// [storage]: macro@storage
+/// [entrypoint]: macro@entrypoint
 /// [`StorageType`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.StorageType.html
+/// [`erc7201_slot`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/fn.erc7201_slot.html
+/// [`ERC-7201`]: https://eips.ethereum.org/EIPS/eip-7201
 /// [overview]: https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#storage
 /// [sdk]: https://docs.rs/stylus-sdk/latest/stylus_sdk/index.html
 #[proc_macro_attribute]
@@ -146,9 +172,6 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 ///
 /// For example, `IService` will have a `make_payment` method that accepts an [`Address`] and returns a [`B256`].
 ///
-/// Currently only functions are supported, and any other items in the interface will cause an
-/// error.
-///
 /// ```ignore
 /// pub fn do_call(account: IService, user: Address) -> Result<String, Error> {
 ///     let config = Call::new()
@@ -162,6 +185,158 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// Observe the casing change. [`sol_interface!`] computes the selector based on the exact name passed in,
 /// which should almost always be `CamelCase`. For aesthetics, the rust functions will instead use `snake_case`.
 ///
+/// Each interface also implements [`Interface`], which allows contracts to be checked against it
+/// via [`#[public(implements(...))]`][public].
+///
+/// # Named return values
+///
+/// Functions that return several named values produce a struct with those fields, which is generated
+/// alongside the interface's other types and named after the method.
+///
+/// ```ignore
+/// sol_interface! {
+///     interface IUniswapV2Pair {
+///         function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 timestamp);
+///     }
+/// }
+///
+/// let reserves: i_uniswap_v_2_pair::GetReservesReturn = pair.get_reserves(self)?;
+/// let price = reserves.reserve1 / reserves.reserve0;
+/// ```
+///
+/// Should another type in the interface share the struct's name, the method must be renamed via
+/// `rust_name`, as described under [overloading](#overloading).
+///
+/// # Handling failures
+///
+/// Each method also has a `try_` variant, which returns a [`CallOutcome`] instead of an error. This
+/// reports whether the call succeeded, along with the return or revert data and the gas used,
+/// allowing for Solidity-style `try`/`catch`.
+///
+/// ```ignore
+/// let outcome = pool.try_liquidate(Call::new_in(self).gas(500_000), account);
+/// if !outcome.success {
+///     // try a different pool
+/// }
+/// ```
+///
+/// # Encoding calls
+///
+/// Each method also has an `encode_` function that produces its calldata without making a call, and
+/// a `decode_` function for its return data. These are useful for batching calls via a multicall
+/// contract, encoding governance proposals, or routing calls with [`delegate_call`].
+///
+/// ```ignore
+/// let calldata = IService::encode_make_payment(user);
+/// let returned = unsafe { delegate_call(self, service, &calldata)? };
+/// let result: String = IService::decode_make_payment_return(&returned)?;
+/// ```
+///
+/// # Batching calls
+///
+/// Each method also has a `prepare_` variant, which produces a [`PendingCall`] for use with a
+/// [`Batch`]. Batches make their calls one after another, or all at once via an aggregator contract
+/// like [`Multicall3`], and return each call's result along with whether it was allowed to fail.
+///
+/// ```ignore
+/// let mut batch = Batch::new().aggregator(MULTICALL3);
+/// let price = batch.add(oracle.prepare_price(token));
+/// let balance = batch.add_allow_failure(vault.prepare_balance_of(user));
+///
+/// let results = batch.static_call(Call::new_in(self))?;
+/// let price = results.get(price)?;
+/// let balance = results.get(balance).unwrap_or_default();
+/// ```
+///
+/// # Overloading
+///
+/// Overloaded functions each keep their own selector, and are told apart by suffixing their Rust
+/// names with their index among the overloads. The `rust_name` attribute chooses a name instead.
+///
+/// ```ignore
+/// sol_interface! {
+///     interface IERC721 {
+///         function safeTransferFrom(address from, address to, uint256 id) external;
+///
+///         #[rust_name = "safe_transfer_from_with_data"]
+///         function safeTransferFrom(address from, address to, uint256 id, bytes data) external;
+///
+///         function mint(address to) external;                   // mint_0
+///         function mint(address to, uint256 amount) external;   // mint_1
+///     }
+/// }
+/// ```
+///
+/// Inherited functions come before those of the child interface when numbering overloads.
+///
+/// # Inheritance
+///
+/// Interfaces may inherit others declared earlier in the same [`sol_interface!`], or in a previous
+/// invocation that is in scope. The generated `struct` then exposes the inherited methods too.
+///
+/// ```ignore
+/// sol_interface! {
+///     interface IERC20 {
+///         function balanceOf(address owner) external view returns (uint256);
+///     }
+/// }
+///
+/// sol_interface! {
+///     interface IERC20Metadata is IERC20 {
+///         function decimals() external view returns (uint8);
+///     }
+/// }
+/// ```
+///
+/// Interfaces declared in another module of the same crate can be inherited by importing them, just
+/// like any other type. Interfaces from other crates can't be inherited, since the macro that makes
+/// this work is only exported within the crate, and must be redeclared instead. The crate must also
+/// be able to name `stylus_sdk`, which the macro expands to.
+///
+/// # Events, errors, and types
+///
+/// Interfaces may also declare `event`, `error`, `struct`, and `enum` items. These are generated
+/// with [`sol!`][sol] in a module named after the interface in `snake_case`, which also contains an
+/// `<Interface>Errors` enum for decoding any of the interface's errors from revert data.
+///
+/// ```ignore
+/// sol_interface! {
+///     interface IService {
+///         struct Payment { address user; uint256 amount; }
+///         error Unauthorized(address caller);
+///
+///         function pay(Payment calldata payment) external;
+///     }
+/// }
+///
+/// let payment = i_service::Payment { user, amount };
+/// match service.pay(self, payment) {
+///     Err(Error::Revert(data)) => match i_service::IServiceErrors::abi_decode(&data, true) {
+///         Ok(i_service::IServiceErrors::Unauthorized(err)) => /* ... */,
+///         _ => /* ... */,
+///     },
+///     result => /* ... */,
+/// }
+/// ```
+///
+/// Structs and enums may be used as parameters and return types. Child interfaces get their own
+/// copies of the items they inherit.
+///
+/// # JSON ABIs
+///
+/// Interfaces may also be loaded from a JSON ABI, such as one produced by `solc` or Foundry. The path is
+/// relative to the crate's `Cargo.toml`, and may point to either the ABI itself or a compiler artifact
+/// containing it.
+///
+/// ```ignore
+/// sol_interface!(IERC20, "abi/IERC20.json");
+/// ```
+///
+/// This generates the same code as the equivalent Solidity interface, including its events and errors.
+/// Structs from different contracts that share a name but not their fields are qualified by their
+/// contract, as in `B_Info` for `B.Info`. Functions in older ABIs without a `stateMutability` are
+/// read via their `constant` and `payable` flags.
+///
 /// # Reentrant calls
 ///
 /// Contracts that opt into reentrancy via the `reentrant` feature flag require extra care.
@@ -230,6 +405,7 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 ///
 /// [sol_interface]: macro@sol_interface
 /// [public]: macro@public
+/// [sol]: https://docs.rs/alloy-sol-macro/latest/alloy_sol_macro/macro.sol.html
 /// [`TopLevelStorage`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.TopLevelStorage.html
 /// [`StorageCache`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageCache.html
 /// [`flush`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageCache.html#method.flush
@@ -237,6 +413,12 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// [`Address`]: https://docs.rs/alloy-primitives/latest/alloy_primitives/struct.Address.html
 /// [`B256`]: https://docs.rs/alloy-primitives/latest/alloy_primitives/aliases/type.B256.html
 /// [`Call`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/struct.Call.html
+/// [`Interface`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/abi/trait.Interface.html
+/// [`CallOutcome`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/struct.CallOutcome.html
+/// [`delegate_call`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/fn.delegate_call.html
+/// [`PendingCall`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/struct.PendingCall.html
+/// [`Batch`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/struct.Batch.html
+/// [`Multicall3`]: https://github.com/mds1/multicall
 #[proc_macro]
 pub fn sol_interface(input: TokenStream) -> TokenStream {
     calls::sol_interface(input)
@@ -310,6 +492,31 @@ pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
     methods::error::derive_solidity_error(input)
 }
 
+/// Implements [`Eip712`] for a `struct`, providing its [`EIP-712`] type string, typehash,
+/// and struct hash.
+///
+/// ```ignore
+/// #[derive(Eip712)]
+/// pub struct Order {
+///     maker: Address,
+///     token_ids: Vec<U256>,    // encoded as `uint256[] tokenIds`
+///     expiry: u64,
+/// }
+///
+/// let digest = Domain::new("Exchange", "1").hash_typed_data(&order);
+/// ```
+///
+/// As with method names in [`#[public]`][public], member names are converted to `camelCase`. Fields
+/// may be other [`Eip712`] structs, whose types are appended to the type string as the standard requires.
+///
+/// [`Eip712`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/crypto/eip712/trait.Eip712.html
+/// [`EIP-712`]: https://eips.ethereum.org/EIPS/eip-712
+/// [public]: macro@public
+#[proc_macro_derive(Eip712)]
+pub fn derive_eip712(input: TokenStream) -> TokenStream {
+    eip712::derive_eip712(input)
+}
+
 /// Defines the entrypoint, which is where Stylus execution begins.
 /// Without it the contract will fail to pass [`cargo stylus check`][check].
 /// Most commonly this macro is used to annotate the top level storage `struct`.
@@ -435,6 +642,25 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// Please refer to the [SDK Feature Overview][overview] for more information on defining methods.
 ///
+/// # `#[fallback]`
+///
+/// As in Solidity, a single method may handle calls that match no selector, including those with
+/// calldata too short to hold one. The method receives the raw calldata and returns raw output.
+///
+/// ```ignore
+/// #[public]
+/// impl Proxy {
+///     #[fallback]
+///     #[payable]
+///     pub fn fallback(&mut self, input: &[u8]) -> ArbResult {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// Only after every inherited method fails to match is the fallback called. If `Self` has none,
+/// the fallbacks of inherited types are tried in order.
+///
 /// # Inheritance, `#[inherit]`, and `#[borrow]`
 ///
 /// Composition in Rust follows that of Solidity. Types that implement [`Router`], the trait that
@@ -498,6 +724,36 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [`Router`] composition is more configurable. The motivation for this becomes clearer in complex
 /// cases of multi-level inheritance, which we intend to improve.
 ///
+/// # Implementing interfaces
+///
+/// Interfaces defined with [`sol_interface!`][sol_interface] can be checked against at compile
+/// time via `implements`. Every function of the interface must then have a matching selector,
+/// return type, and state mutability among the methods of the router, including those it inherits.
+/// As in Solidity, methods may be stricter than the interface requires, such as `pure` instead of `view`.
+///
+/// ```ignore
+/// sol_interface! {
+///     interface IERC20 {
+///         function balanceOf(address owner) external view returns (uint256);
+///         function transfer(address to, uint256 value) external returns (bool);
+///     }
+/// }
+///
+/// #[public(implements(IERC20))]
+/// impl Erc20 {
+///     pub fn balance_of(&self, owner: Address) -> U256 {
+///         ...
+///     }
+///
+///     pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// Generic routers are checked where they are used as the [`#[entrypoint]`][entrypoint] instead.
+/// When exporting the ABI, implemented interfaces appear in the `is` clause.
+///
 /// # Exporting a Solidity interface
 ///
 /// Recall that Stylus contracts are fully interoperable across all languages, including Solidity.
@@ -554,6 +810,7 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [pure]: https://docs.soliditylang.org/en/develop/contracts.html#pure-functions
 /// [cli]: https://github.com/OffchainLabs/cargo-stylus#exporting-solidity-abis
 /// [dfs]: https://en.wikipedia.org/wiki/Depth-first_search
+/// [sol_interface]: macro@sol_interface
 #[proc_macro_attribute]
 pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
     methods::public::public(attr, input)
@@ -29,13 +29,19 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
                     use core::convert::TryInto;
                     use alloc::vec;
 
+                    let mut storage = unsafe { <#name as StorageType>::new(U256::ZERO, 0) };
                     if input.len() < 4 {
+                        if let Some(res) = <#name as Router<_>>::fallback(&mut storage, &input) {
+                            return res;
+                        }
                         console!("calldata too short: {}", hex::encode(input));
                         return Err(vec![]);
                     }
                     let selector = u32::from_be_bytes(TryInto::try_into(&input[..4]).unwrap());
-                    let mut storage = unsafe { <#name as StorageType>::new(U256::ZERO, 0) };
-                    match <#name as Router<_>>::route(&mut storage, selector, &input[4..]) {
+                    if let Some(res) = <#name as Router<_>>::route(&mut storage, selector, &input[4..]) {
+                        return res;
+                    }
+                    match <#name as Router<_>>::fallback(&mut storage, &input) {
                         Some(res) => res,
                         None => {
                             console!("unknown method selector: {selector:08x}");
@@ -4,7 +4,7 @@
 use crate::types::Purity;
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span};
+use proc_macro2::Ident;
 use quote::{quote, quote_spanned};
 use std::mem;
 use syn::{
@@ -17,9 +17,8 @@ use syn::{
 };
 
 pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
-    if !attr.is_empty() {
-        error!(Span::mixed_site(), "this macro is not configurable");
-    }
+    let args = parse_macro_input!(attr as PublicArgs);
+    let implements = args.implements;
 
     let mut input = parse_macro_input!(input as ItemImpl);
     let mut selectors = quote!();
@@ -28,6 +27,8 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
     let mut types = vec![];
     let mut override_selectors = quote!();
     let mut selector_consts = vec![];
+    let mut method_infos = quote!();
+    let mut fallback = None;
 
     for item in input.items.iter_mut() {
         let ImplItem::Method(method) = item else {
@@ -37,6 +38,7 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         // see if user chose a purity or selector
         let mut purity = None;
         let mut override_name = None;
+        let mut is_fallback = false;
         for attr in mem::take(&mut method.attrs) {
             let Some(ident) = attr.path.get_ident() else {
                 method.attrs.push(attr);
@@ -63,9 +65,23 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 override_name = Some(args.name);
                 continue;
             }
+            if *ident == "fallback" {
+                if !attr.tokens.is_empty() {
+                    error!(attr.tokens, "attribute does not take parameters");
+                }
+                if fallback.is_some() || is_fallback {
+                    error!(attr.path, "more than one fallback method");
+                }
+                is_fallback = true;
+                continue;
+            }
             method.attrs.push(attr);
         }
 
+        if is_fallback && override_name.is_some() {
+            error!(method.sig.ident, "fallback methods don't have selectors");
+        }
+
         use Purity::*;
 
         // determine purity if not
@@ -133,6 +149,34 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             quote! { storage, }
         };
 
+        // the fallback handles raw calldata rather than having a selector
+        if is_fallback {
+            if args.len() != 1 {
+                error!(
+                    method.sig.inputs,
+                    "fallback methods must take just the calldata"
+                );
+            }
+            fallback = Some(quote! {
+                #deny_value
+                let result: stylus_sdk::ArbResult = Self::#name(#storage input);
+                Some(result)
+            });
+
+            if cfg!(feature = "export-abi") {
+                let sol_purity = match purity {
+                    Payable => " payable",
+                    _ => "",
+                };
+                abi.extend(quote! {
+                    write!(f, "\n    fallback(bytes calldata input) external")?;
+                    write!(f, #sol_purity)?;
+                    writeln!(f, " returns (bytes memory);")?;
+                });
+            }
+            continue;
+        }
+
         // get the solidity args
         let mut expand_args = vec![];
         for (index, (_, ty)) in args.iter().enumerate() {
@@ -163,7 +207,18 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             "function {} cannot be overriden with function marked {:?}",
             name, purity
         );
-        selector_consts.push((constant.clone(), sdk_purity, error_msg));
+        selector_consts.push((constant.clone(), sdk_purity.clone(), error_msg));
+
+        let returns = match &method.sig.output {
+            ReturnType::Default => quote! { stylus_sdk::abi::ConstString::new("()") },
+            ReturnType::Type(_, ty) => quote! {
+                <<#ty as stylus_sdk::abi::internal::AbiResult>::OkType as stylus_sdk::abi::AbiType>::ABI
+            },
+        };
+        method_infos.extend(quote! {
+            #[allow(non_upper_case_globals)]
+            #constant => return Some((#sdk_purity, #returns)),
+        });
 
         let in_span = method.sig.inputs.span();
         let decode_inputs = quote_spanned! { in_span => <(#( #arg_types, )*) as AbiType>::SolType };
@@ -247,6 +302,36 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     });
 
+    // use our own fallback, or else that of the first inherited router with one
+    let fallback = match fallback {
+        Some(fallback) => fallback,
+        None => {
+            let inherit_fallbacks = inherits.iter().map(|ty| {
+                quote! {
+                    if let Some(result) = <#ty as Router<S>>::fallback(storage, input) {
+                        return Some(result);
+                    }
+                }
+            });
+            quote! {
+                #(#inherit_fallbacks)*
+                None
+            }
+        }
+    };
+
+    // list our selectors, then those we inherit that we don't override
+    let own_selectors = selector_consts.iter().map(|(constant, ..)| constant);
+    let inherit_selectors = inherits.iter().map(|ty| {
+        quote! {
+            for selector in <#ty as stylus_sdk::abi::Selectors>::selectors() {
+                if !selectors.contains(&selector) {
+                    selectors.push(selector);
+                }
+            }
+        }
+    });
+
     // ensure we can actually borrow the things we inherit
     let borrow_clauses = inherits.iter().map(|ty| {
         quote! {
@@ -282,6 +367,41 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
     });
+    let inherit_methods = inherits.iter().map(|ty| {
+        quote! {
+            if let Some(method) = <#ty>::__stylus_method(selector) {
+                return Some(method);
+            }
+        }
+    });
+    let check_implements = implements.iter().map(|ty| {
+        quote! {
+            let functions = <#ty as stylus_sdk::abi::Interface>::FUNCTIONS;
+            let mut i = 0;
+            while i < functions.len() {
+                let func = &functions[i];
+                stylus_sdk::abi::internal::assert_implements(
+                    <#ty as stylus_sdk::abi::Interface>::NAME,
+                    func,
+                    Self::__stylus_method(func.selector),
+                );
+                i += 1;
+            }
+        }
+    });
+
+    // generic routers are instead checked by the entrypoint
+    let (assert_implements, entrypoint_implements) = match generic_params.is_empty() {
+        true => (
+            quote! {
+                const _: () = {
+                    <#self_ty>::__stylus_assert_implements();
+                };
+            },
+            quote!(),
+        ),
+        false => (quote!(), quote! { Self::__stylus_assert_implements(); }),
+    };
 
     // implement Router with inheritance
     let mut router = quote! {
@@ -314,6 +434,24 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                     }
                 }
             }
+
+            #[inline(always)]
+            #[allow(unused)]
+            fn fallback(storage: &mut S, input: &[u8]) -> Option<stylus_sdk::ArbResult> {
+                use stylus_sdk::abi::{internal, Router};
+                #fallback
+            }
+        }
+
+        impl<#generic_params> stylus_sdk::abi::Selectors for #self_ty where #where_clauses {
+            fn selectors() -> alloc::vec::Vec<u32> {
+                use stylus_sdk::function_selector;
+                #selectors
+                #[allow(unused_mut)]
+                let mut selectors = alloc::vec![#(#own_selectors),*];
+                #(#inherit_selectors)*
+                selectors
+            }
         }
 
         // implement checks for method overriding.
@@ -340,8 +478,33 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 use stylus_sdk::function_selector;
                 #selectors
                 #(#check_overrides)*
+                #entrypoint_implements
+            }
+
+            #[doc(hidden)]
+            /// Finds the purity and return ABI of the method with the given selector, searching
+            /// inherited routers in the order they are checked.
+            pub const fn __stylus_method(
+                selector: u32,
+            ) -> Option<(stylus_sdk::methods::Purity, stylus_sdk::abi::ConstString)> {
+                use stylus_sdk::function_selector;
+                #selectors
+                match selector {
+                    #method_infos
+                    _ => {}
+                }
+                #(#inherit_methods)*
+                None
+            }
+
+            #[doc(hidden)]
+            /// Check that the router conforms to the interfaces it implements.
+            pub const fn __stylus_assert_implements() {
+                #(#check_implements)*
             }
         }
+
+        #assert_implements
     };
 
     // only collect abi info if enabled
@@ -380,7 +543,7 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
     });
 
     // write the "is" clause in Solidity
-    let mut is_clause = match inherits.is_empty() {
+    let mut is_clause = match inherits.is_empty() && implements.is_empty() {
         true => quote! {},
         false => quote! { write!(f, " is ")?; },
     };
@@ -390,6 +553,12 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             write!(f, "{}I{}", #comma, <#ty as GenerateAbi>::NAME)?;
         }
     }));
+    is_clause.extend(implements.iter().enumerate().map(|(i, ty)| {
+        let comma = if i + inherits.len() > 0 { ", " } else { "" };
+        quote! {
+            write!(f, "{}{}", #comma, <#ty as stylus_sdk::abi::Interface>::NAME)?;
+        }
+    }));
 
     router.extend(quote! {
         impl<#generic_params> stylus_sdk::abi::GenerateAbi for #self_ty where #where_clauses {
@@ -399,8 +568,12 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 use stylus_sdk::abi::{AbiType, GenerateAbi};
                 use stylus_sdk::abi::internal::write_solidity_returns;
                 use stylus_sdk::abi::export::{underscore_if_sol, internal::{InnerType, InnerTypes}};
+                use stylus_sdk::abi::export::internal::{Namespace, NoNamespace, StorageNamespace};
                 use std::collections::HashSet;
                 #(#inherited_abis)*
+                if let Some(namespace) = (&Namespace::<#self_ty>::new()).namespace() {
+                    writeln!(f, "/// @custom:storage-location erc7201:{namespace}")?;
+                }
                 write!(f, "interface I{}", #name)?;
                 #is_clause
                 write!(f, " {{")?;
@@ -415,6 +588,34 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
     router.into()
 }
 
+#[derive(Default)]
+struct PublicArgs {
+    implements: Vec<Type>,
+}
+
+impl Parse for PublicArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = Self::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            match ident.to_string().as_str() {
+                "implements" => {
+                    let content;
+                    let _ = parenthesized!(content in input);
+                    let types = Punctuated::<Type, Token![,]>::parse_separated_nonempty(&content)?;
+                    args.implements.extend(types);
+                }
+                _ => error!(@ident, "Unknown public attribute"),
+            }
+
+            // allow a comma
+            let _: Result<Token![,]> = input.parse();
+        }
+        Ok(args)
+    }
+}
+
 struct InheritsAttr {
     types: Punctuated<Type, Token![,]>,
 }
@@ -5,11 +5,17 @@ use crate::storage::proc::{SolidityField, SolidityFields, SolidityStruct, Solidi
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
 use std::mem;
-use syn::{parse_macro_input, punctuated::Punctuated, Index, ItemStruct, Token, Type};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Ident, Index, ItemStruct, LitStr, Result, Token, Type,
+};
 
 mod proc;
 
-pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn storage(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as StorageArgs);
     let mut input = parse_macro_input!(input as ItemStruct);
 
     let name = &input.ident;
@@ -17,6 +23,7 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
 
     let mut init = quote! {};
     let mut size = quote! {};
+    let mut transient_size = quote! {};
     let mut borrows = quote! {};
 
     for (field_index, field) in input.fields.iter_mut().enumerate() {
@@ -78,6 +85,7 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
             continue;
         };
 
+        // transient fields are laid out separately, so they don't shift persistent ones
         init.extend(quote! {
             #ident: {
                 let bytes = <#ty as storage::StorageType>::SLOT_BYTES;
@@ -88,12 +96,28 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
                 }
                 space -= bytes;
 
-                let root = root + alloy_primitives::U256::from(slot);
-                let field = <#ty as storage::StorageType>::new(root, space as u8);
+                let transient_bytes = <#ty as storage::StorageType>::TRANSIENT_SLOT_BYTES;
+                let transient_words = <#ty as storage::StorageType>::TRANSIENT_REQUIRED_SLOTS;
+                if transient_space < transient_bytes {
+                    transient_space = 32;
+                    transient_slot += 1;
+                }
+                transient_space -= transient_bytes;
+
+                let field = <#ty as storage::StorageType>::new_with_transient(
+                    root + alloy_primitives::U256::from(slot),
+                    space as u8,
+                    transient_root + alloy_primitives::U256::from(transient_slot),
+                    transient_space as u8,
+                );
                 if words > 0 {
                     slot += words;
                     space = 32;
                 }
+                if transient_words > 0 {
+                    transient_slot += transient_words;
+                    transient_space = 32;
+                }
                 field
             },
         });
@@ -113,38 +137,114 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
                 space -= bytes;
             }
         });
+
+        // mirrors the transient layout in `init`
+        transient_size.extend(quote! {
+            let bytes = <#ty as storage::StorageType>::TRANSIENT_SLOT_BYTES;
+            let words = <#ty as storage::StorageType>::TRANSIENT_REQUIRED_SLOTS;
+
+            if space < bytes {
+                space = 32;
+                total += 1;
+            }
+            space -= bytes;
+            if words > 0 {
+                total += words;
+                space = 32;
+            }
+        });
     }
 
-    let expanded = quote! {
-        #input
+    // namespaced structs live at their own root, taking up no space in their parents
+    let layout = match &args.namespace {
+        Some(namespace) => quote! {
+            const NAMESPACE: Option<&'static str> = Some(#namespace);
+            const SLOT_BYTES: usize = 0;
+            const REQUIRED_SLOTS: usize = 0;
+        },
+        None => quote! {
+            // start a new word, unless there's nothing to store
+            const SLOT_BYTES: usize = match Self::required_slots() {
+                0 => 0,
+                _ => 32,
+            };
+            const REQUIRED_SLOTS: usize = Self::required_slots();
+            const TRANSIENT_SLOT_BYTES: usize = match Self::transient_slots() {
+                0 => 0,
+                _ => 32,
+            };
+            const TRANSIENT_REQUIRED_SLOTS: usize = Self::transient_slots();
+        },
+    };
+    let root = match &args.namespace {
+        Some(namespace) => quote! {
+            const ROOT: stylus_sdk::alloy_primitives::U256 = storage::erc7201_slot(#namespace);
+            let _ = (root, offset, transient_root, transient_offset);
+            let root = ROOT;
+            let transient_root = ROOT;
+        },
+        None => quote! {
+            debug_assert!(offset == 0 || Self::SLOT_BYTES == 0);
+            debug_assert!(transient_offset == 0 || Self::TRANSIENT_SLOT_BYTES == 0);
+        },
+    };
+    let required_slots = match &args.namespace {
+        Some(_) => quote! {},
+        None => quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                const fn required_slots() -> usize {
+                    use stylus_sdk::storage;
+                    let mut total: usize = 0;
+                    let mut space: usize = 32;
+                    #size
+                    if space != 32 || (total == 0 && Self::transient_slots() == 0) {
+                        total += 1;
+                    }
+                    total
+                }
 
-        impl #impl_generics #name #ty_generics #where_clause {
-            const fn required_slots() -> usize {
-                use stylus_sdk::storage;
-                let mut total: usize = 0;
-                let mut space: usize = 32;
-                #size
-                if space != 32 || total == 0 {
-                    total += 1;
+                const fn transient_slots() -> usize {
+                    use stylus_sdk::storage;
+                    let mut total: usize = 0;
+                    let mut space: usize = 32;
+                    #transient_size
+                    if space != 32 {
+                        total += 1;
+                    }
+                    total
                 }
-                total
             }
-        }
+        },
+    };
+
+    let expanded = quote! {
+        #input
+
+        #required_slots
 
         impl #impl_generics stylus_sdk::storage::StorageType for #name #ty_generics #where_clause {
             type Wraps<'a> = stylus_sdk::storage::StorageGuard<'a, Self> where Self: 'a;
             type WrapsMut<'a> = stylus_sdk::storage::StorageGuardMut<'a, Self> where Self: 'a;
 
-            // start a new word
-            const SLOT_BYTES: usize = 32;
-            const REQUIRED_SLOTS: usize = Self::required_slots();
+            #layout
+
+            unsafe fn new(root: stylus_sdk::alloy_primitives::U256, offset: u8) -> Self {
+                Self::new_with_transient(root, offset, root, 0)
+            }
 
-            unsafe fn new(mut root: stylus_sdk::alloy_primitives::U256, offset: u8) -> Self {
+            unsafe fn new_with_transient(
+                root: stylus_sdk::alloy_primitives::U256,
+                offset: u8,
+                transient_root: stylus_sdk::alloy_primitives::U256,
+                transient_offset: u8,
+            ) -> Self {
                 use stylus_sdk::{storage, alloy_primitives};
-                debug_assert!(offset == 0);
+                #root
 
                 let mut space: usize = 32;
                 let mut slot: usize = 0;
+                let mut transient_space: usize = 32;
+                let mut transient_slot: usize = 0;
                 let accessor = Self {
                     #init
                 };
@@ -165,6 +265,36 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+#[derive(Default)]
+struct StorageArgs {
+    namespace: Option<LitStr>,
+}
+
+impl Parse for StorageArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = Self::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            match ident.to_string().as_str() {
+                "namespace" => {
+                    let _: Token![=] = input.parse()?;
+                    let namespace: LitStr = input.parse()?;
+                    if namespace.value().is_empty() {
+                        error!(@namespace, "namespace must not be empty");
+                    }
+                    args.namespace = Some(namespace);
+                }
+                _ => error!(@ident, "Unknown storage attribute"),
+            }
+
+            // allow a comma
+            let _: Result<Token![,]> = input.parse();
+        }
+        Ok(args)
+    }
+}
+
 pub fn sol_storage(input: TokenStream) -> TokenStream {
     let SolidityStructs(decls) = parse_macro_input!(input as SolidityStructs);
     let mut out = quote!();
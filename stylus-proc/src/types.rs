@@ -4,9 +4,9 @@
 use alloy_sol_types::SolType;
 use proc_macro2::TokenStream;
 use quote::quote;
-use std::{borrow::Cow, fmt::Display, num::NonZeroU16, str::FromStr};
+use std::{borrow::Cow, collections::HashMap, fmt::Display, num::NonZeroU16, str::FromStr};
 use syn::Token;
-use syn_solidity::Type;
+use syn_solidity::{Spanned, Type};
 
 /// The purity of a Solidity method
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -69,8 +69,14 @@ impl From<Option<Token![mut]>> for Purity {
     }
 }
 
+/// The alloy path and ABI of each struct and enum declared in an interface, keyed by name.
+pub type CustomTypes = HashMap<String, (String, String)>;
+
 /// Returns the alloy path and ABI for a given Solidity type
-pub fn solidity_type_info(ty: &Type) -> (Cow<'static, str>, Cow<'static, str>) {
+pub fn solidity_type_info(
+    ty: &Type,
+    custom: &CustomTypes,
+) -> syn::Result<(Cow<'static, str>, Cow<'static, str>)> {
     use alloy_sol_types::sol_data;
 
     macro_rules! abi {
@@ -89,14 +95,14 @@ pub fn solidity_type_info(ty: &Type) -> (Cow<'static, str>, Cow<'static, str>) {
             (path!(stringify!($ty)), sol_data::$ty::SOL_NAME.into())
         };
     }
-    match ty {
+    Ok(match ty {
         Type::Bool(_) => simple!(Bool),
         Type::Address(_, _) => simple!(Address),
         Type::String(_) => simple!(String),
         Type::Bytes(_) => simple!(Bytes),
         Type::FixedBytes(_, size) => (
             format!("stylus_sdk::alloy_sol_types::sol_data::FixedBytes<{size}>").into(),
-            abi!("bytes[{size}]"),
+            abi!("bytes{size}"),
         ),
         Type::Uint(_, size) => {
             let size = size.unwrap_or(NonZeroU16::new(256).unwrap());
@@ -107,7 +113,7 @@ pub fn solidity_type_info(ty: &Type) -> (Cow<'static, str>, Cow<'static, str>) {
             (path!("Int<{size}>"), abi!("int{size}"))
         }
         Type::Array(ty) => {
-            let (path, abi) = solidity_type_info(&ty.ty);
+            let (path, abi) = solidity_type_info(&ty.ty, custom)?;
             match ty.size() {
                 Some(size) => (path!("FixedArray<{path}, {size}>"), abi!("{abi}[{size}]")),
                 None => (path!("Array<{path}>"), abi!("{abi}[]")),
@@ -117,7 +123,7 @@ pub fn solidity_type_info(ty: &Type) -> (Cow<'static, str>, Cow<'static, str>) {
             if tup.types.is_empty() {
                 ("()".into(), "()".into())
             } else if tup.types.len() == 1 {
-                solidity_type_info(&tup.types[0])
+                solidity_type_info(&tup.types[0], custom)?
             } else {
                 let mut path = "(".to_string();
                 let mut abi = "(".to_string();
@@ -126,7 +132,7 @@ pub fn solidity_type_info(ty: &Type) -> (Cow<'static, str>, Cow<'static, str>) {
                         path += ", ";
                         abi += ",";
                     }
-                    let (inner_path, inner_abi) = solidity_type_info(ty);
+                    let (inner_path, inner_abi) = solidity_type_info(ty, custom)?;
                     path += &inner_path;
                     abi += &inner_abi;
                 }
@@ -135,6 +141,13 @@ pub fn solidity_type_info(ty: &Type) -> (Cow<'static, str>, Cow<'static, str>) {
                 (path.into(), abi.into())
             }
         }
+        Type::Custom(name) => match custom.get(&name.to_string()) {
+            Some((path, abi)) => (path.clone().into(), abi.clone().into()),
+            None => {
+                let msg = format!("unknown Solidity type {name}");
+                return Err(syn::Error::new(name.span(), msg));
+            }
+        },
         _ => todo!("Solidity type {ty} is not yet implemented in sol_interface!"),
-    }
+    })
 }
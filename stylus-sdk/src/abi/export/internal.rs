@@ -4,11 +4,11 @@
 //! This module provides functions for code generated by `stylus-sdk-proc` for the `export-abi` command.
 //! Most users shouldn't call these.
 
-use core::any::TypeId;
+use core::{any::TypeId, marker::PhantomData};
 
 use alloy_primitives::{Address, FixedBytes, Signed, Uint};
 
-use crate::abi::Bytes;
+use crate::{abi::Bytes, storage::StorageType};
 
 /// Represents a unique Solidity Type.
 pub struct InnerType {
@@ -83,3 +83,41 @@ macro_rules! impl_tuple {
 }
 
 impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X);
+
+/// Looks up the [`ERC-7201`] namespace of a `#[public]` type without requiring it be storage.
+///
+/// Calling `(&Namespace::<T>::new()).namespace()` resolves to [`StorageNamespace`] when `T`
+/// implements [`StorageType`], and to [`NoNamespace`] otherwise.
+///
+/// [`ERC-7201`]: https://eips.ethereum.org/EIPS/eip-7201
+pub struct Namespace<T>(PhantomData<T>);
+
+impl<T> Namespace<T> {
+    /// Creates a new namespace lookup.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Gets the namespace of storage types.
+pub trait StorageNamespace {
+    /// The type's namespace, if it has one.
+    fn namespace(&self) -> Option<&'static str>;
+}
+
+impl<T: StorageType> StorageNamespace for Namespace<T> {
+    fn namespace(&self) -> Option<&'static str> {
+        T::NAMESPACE
+    }
+}
+
+/// Fallback for types that aren't storage, which have no namespace.
+pub trait NoNamespace {
+    /// Always [`None`].
+    fn namespace(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl<T> NoNamespace for &Namespace<T> {}
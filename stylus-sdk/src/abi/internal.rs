@@ -4,7 +4,12 @@
 //! This module provides functions for code generated by `stylus-sdk-proc`.
 //! Most users shouldn't call these.
 
-use crate::{abi::AbiType, console, msg, ArbResult};
+use crate::{
+    abi::{AbiType, ConstString, InterfaceFunction},
+    console,
+    methods::Purity,
+    msg, ArbResult,
+};
 use alloc::{vec, vec::Vec};
 use alloy_primitives::U256;
 use alloy_sol_types::SolType;
@@ -87,3 +92,46 @@ where
         write!(f, " returns ({abi})")
     }
 }
+
+/// Checks that the method with a function's selector, if any, conforms to the interface.
+/// The method is given by its purity and the ABI of its return values.
+pub const fn assert_implements(
+    interface: &str,
+    func: &InterfaceFunction,
+    method: Option<(Purity, ConstString)>,
+) {
+    let error = match method {
+        None => ConstString::new(" is not implemented"),
+        Some((purity, _)) if !func.purity.allow_override(purity) => {
+            ConstString::new(" must be ").concat(ConstString::new(func.purity.as_str()))
+        }
+        Some((_, returns)) if !bytes_eq(returns.as_bytes(), func.returns.as_bytes()) => {
+            ConstString::new(" must return ")
+                .concat(ConstString::new(func.returns))
+                .concat(ConstString::new(", not "))
+                .concat(returns)
+        }
+        _ => return,
+    };
+    let msg = ConstString::new("function ")
+        .concat(ConstString::new(func.signature))
+        .concat(ConstString::new(" of "))
+        .concat(ConstString::new(interface))
+        .concat(error);
+    panic!("{}", msg.as_str());
+}
+
+/// Compares two byte slices in a `const` context.
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
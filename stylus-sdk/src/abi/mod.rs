@@ -14,7 +14,8 @@
 //!
 //! [prelude]: crate::prelude
 
-use crate::{storage::TopLevelStorage, ArbResult};
+use crate::{methods::Purity, storage::TopLevelStorage, ArbResult};
+use alloc::vec::Vec;
 use alloy_sol_types::SolType;
 use core::borrow::BorrowMut;
 
@@ -48,6 +49,48 @@ where
     /// Routes add via `#[inherit]` will only execute if no match is found among `Self`.
     /// This means that it is possible to override a method by redefining it in `Self`.
     fn route(storage: &mut S, selector: u32, input: &[u8]) -> Option<ArbResult>;
+
+    /// Handles calldata that no method matches, including that too short to hold a selector,
+    /// returning `None` if there's no `#[fallback]` method.
+    /// As with [`route`](Router::route), inherited fallbacks only execute if `Self` has none.
+    fn fallback(storage: &mut S, input: &[u8]) -> Option<ArbResult> {
+        let _ = (storage, input);
+        None
+    }
+}
+
+/// Lists the selectors a [`Router`] handles, such as for adding it as a facet of a diamond.
+/// This trait is automatically implemented via `#[public]`.
+pub trait Selectors {
+    /// Returns the selectors of the methods of `Self`, followed by those it inherits.
+    /// Overridden methods are only listed once.
+    fn selectors() -> Vec<u32>;
+}
+
+/// A Solidity interface that [`Router`] implementations can be checked against.
+/// This trait is automatically implemented by [`sol_interface!`], and may be checked via
+/// `#[public(implements(...))]`.
+///
+/// [`sol_interface!`]: crate::prelude::sol_interface
+pub trait Interface {
+    /// The name of the interface.
+    const NAME: &'static str;
+
+    /// The functions of the interface, including those it inherits.
+    const FUNCTIONS: &'static [InterfaceFunction];
+}
+
+/// A function of an [`Interface`].
+#[derive(Debug, Clone, Copy)]
+pub struct InterfaceFunction {
+    /// The Solidity signature, such as `transfer(address,uint256)`.
+    pub signature: &'static str,
+    /// The function selector, which is derived from the signature.
+    pub selector: u32,
+    /// The state mutability.
+    pub purity: Purity,
+    /// The ABI of the return values, such as `bool` or `(uint256,bool)`, or `()` if there are none.
+    pub returns: &'static str,
 }
 
 /// Provides a mapping of Rust to Solidity types.
@@ -106,3 +149,31 @@ fn test_function_selector() {
     const TEST_SELECTOR: [u8; 4] = function_selector!("foo", Address, U256);
     assert_eq!(TEST_SELECTOR, 0xbd0d639f_u32.to_be_bytes());
 }
+
+#[test]
+fn test_assert_implements() {
+    use internal::assert_implements;
+    use std::panic::catch_unwind;
+
+    const BALANCE_OF: InterfaceFunction = InterfaceFunction {
+        signature: "balanceOf(address)",
+        selector: 0x70a08231,
+        purity: Purity::View,
+        returns: "uint256",
+    };
+    let uint256 = || ConstString::new("uint256");
+    assert_implements("IERC20", &BALANCE_OF, Some((Purity::View, uint256())));
+    assert_implements("IERC20", &BALANCE_OF, Some((Purity::Pure, uint256())));
+
+    let missing = catch_unwind(|| assert_implements("IERC20", &BALANCE_OF, None));
+    let mutable = catch_unwind(|| {
+        assert_implements("IERC20", &BALANCE_OF, Some((Purity::Write, uint256())));
+    });
+    let returns = catch_unwind(|| {
+        let bool = ConstString::new("bool");
+        assert_implements("IERC20", &BALANCE_OF, Some((Purity::View, bool)));
+    });
+    assert!(missing.is_err());
+    assert!(mutable.is_err());
+    assert!(returns.is_err());
+}
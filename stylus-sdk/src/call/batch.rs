@@ -0,0 +1,294 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{Error, NonPayableCallContext, RawCall, StaticCallContext};
+use crate::abi::Bytes;
+use alloc::vec::Vec;
+use alloy_primitives::{address, Address};
+use alloy_sol_types::{sol_data, SolType};
+
+#[cfg(feature = "reentrant")]
+use crate::storage::Storage;
+
+/// The address of [`Multicall3`], which is deployed to the same address on most chains.
+///
+/// [`Multicall3`]: https://github.com/mds1/multicall
+pub const MULTICALL3: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// A call that has been encoded, but not yet made.
+/// The `prepare_` methods of [`sol_interface!`] types produce these for use with [`Batch`].
+///
+/// [`sol_interface!`]: crate::prelude::sol_interface
+pub struct PendingCall<T> {
+    /// The contract to call.
+    pub to: Address,
+    /// The calldata, including the method selector.
+    pub calldata: Vec<u8>,
+    /// Decodes the return data of the call.
+    pub decode: fn(&[u8]) -> Result<T, alloy_sol_types::Error>,
+}
+
+/// Makes many calls at once, either one after another or through an aggregator contract.
+///
+/// ```no_run
+/// use stylus_sdk::call::{Batch, Call, MULTICALL3};
+/// use stylus_sdk::{prelude::*, alloy_primitives::U256};
+/// extern crate alloc;
+///
+/// sol_interface! {
+///     interface IPool {
+///         function price() external view returns (uint256);
+///     }
+/// }
+///
+/// pub fn prices(
+///     storage: &mut impl TopLevelStorage,  // can be generic, but often just &mut self
+///     pool_a: IPool,
+///     pool_b: IPool,
+/// ) -> Result<(U256, Option<U256>), stylus_sdk::call::Error> {
+///     let mut batch = Batch::new().aggregator(MULTICALL3);
+///     let a = batch.add(pool_a.prepare_price());
+///     let b = batch.add_allow_failure(pool_b.prepare_price());
+///
+///     let results = batch.static_call(Call::new_in(storage))?;
+///     let price: U256 = results.get(a)?;
+///     let backup: Option<U256> = results.get(b).ok();
+///     Ok((price, backup))
+/// }
+/// ```
+#[derive(Clone, Default)]
+#[must_use]
+pub struct Batch {
+    aggregator: Option<Address>,
+    calls: Vec<BatchCall>,
+}
+
+#[derive(Clone)]
+struct BatchCall {
+    to: Address,
+    allow_failure: bool,
+    calldata: Vec<u8>,
+}
+
+/// Identifies a call added to a [`Batch`], for retrieving its result from [`BatchResults`].
+pub struct BatchEntry<T> {
+    index: usize,
+    decode: fn(&[u8]) -> Result<T, alloy_sol_types::Error>,
+}
+
+impl<T> Clone for BatchEntry<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BatchEntry<T> {}
+
+/// The results of the calls made by a [`Batch`], in the order they were added.
+#[derive(Clone, Debug)]
+pub struct BatchResults {
+    results: Vec<Result<Vec<u8>, Vec<u8>>>,
+}
+
+impl Batch {
+    /// Begins configuring a batch, which by default makes each call in turn.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Makes the calls through a contract implementing [`Multicall3`]'s `aggregate3` method,
+    /// such as the one at [`MULTICALL3`]. Note that the aggregator is then the sender of each call.
+    ///
+    /// [`Multicall3`]: https://github.com/mds1/multicall
+    pub fn aggregator(mut self, aggregator: Address) -> Self {
+        self.aggregator = Some(aggregator);
+        self
+    }
+
+    /// Adds a call to the batch. Should it fail, the entire batch will fail.
+    pub fn add<T>(&mut self, call: PendingCall<T>) -> BatchEntry<T> {
+        self.push(call, false)
+    }
+
+    /// Adds a call to the batch that is allowed to fail without failing the others.
+    pub fn add_allow_failure<T>(&mut self, call: PendingCall<T>) -> BatchEntry<T> {
+        self.push(call, true)
+    }
+
+    fn push<T>(&mut self, call: PendingCall<T>, allow_failure: bool) -> BatchEntry<T> {
+        self.calls.push(BatchCall {
+            to: call.to,
+            allow_failure,
+            calldata: call.calldata,
+        });
+        BatchEntry {
+            index: self.calls.len() - 1,
+            decode: call.decode,
+        }
+    }
+
+    /// The number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether the batch has no calls.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Static calls each of the contracts, each with the gas limit of the context.
+    /// When using an aggregator, the limit instead applies to the batch as a whole.
+    pub fn static_call(self, context: impl StaticCallContext) -> Result<BatchResults, Error> {
+        #[cfg(feature = "reentrant")]
+        Storage::flush(); // flush storage to persist changes, but don't invalidate the cache
+
+        self.execute(context.gas(), RawCall::new_static)
+    }
+
+    /// Calls each of the contracts, each with the gas limit of the context.
+    /// When using an aggregator, the limit instead applies to the batch as a whole.
+    ///
+    /// Calls made before one that fails are not undone, unless the caller then reverts.
+    pub fn call(self, context: impl NonPayableCallContext) -> Result<BatchResults, Error> {
+        #[cfg(feature = "reentrant")]
+        Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+        self.execute(context.gas(), RawCall::new)
+    }
+
+    fn execute(self, gas: u64, raw: fn() -> RawCall) -> Result<BatchResults, Error> {
+        let Some(aggregator) = self.aggregator else {
+            let mut results = Vec::with_capacity(self.calls.len());
+            for call in self.calls {
+                // safety: the storage cache was handled by the caller
+                #[allow(unused_unsafe)]
+                let result = unsafe { raw().gas(gas).call(call.to, &call.calldata) };
+                match result {
+                    Err(data) if !call.allow_failure => return Err(Error::Revert(data)),
+                    result => results.push(result),
+                }
+            }
+            return Ok(BatchResults { results });
+        };
+
+        let calldata = self.aggregate3_calldata();
+        #[allow(unused_unsafe)]
+        let returned = unsafe { raw().gas(gas).call(aggregator, &calldata) };
+        let returned = returned.map_err(Error::Revert)?;
+
+        type Returns = (sol_data::Array<(sol_data::Bool, sol_data::Bytes)>,);
+        let results = <Returns as SolType>::abi_decode_params(&returned, true)?.0;
+        if results.len() != self.calls.len() {
+            let err = "aggregator returned the wrong number of results";
+            return Err(alloy_sol_types::Error::custom(err).into());
+        }
+        let results = results
+            .into_iter()
+            .map(|(success, data)| match success {
+                true => Ok(data.into()),
+                false => Err(data.into()),
+            })
+            .collect();
+        Ok(BatchResults { results })
+    }
+
+    /// Encodes a call to `aggregate3((address,bool,bytes)[])`.
+    fn aggregate3_calldata(&self) -> Vec<u8> {
+        type Call3 = (sol_data::Address, sol_data::Bool, sol_data::Bytes);
+        let calls: Vec<(Address, bool, alloy_primitives::Bytes)> = self
+            .calls
+            .iter()
+            .map(|call| (call.to, call.allow_failure, call.calldata.clone().into()))
+            .collect();
+
+        let selector = crate::function_selector!("aggregate3", Vec<(Address, bool, Bytes)>);
+        let mut calldata = selector.to_vec();
+        calldata.extend(<(sol_data::Array<Call3>,) as SolType>::abi_encode_params(
+            &(calls,),
+        ));
+        calldata
+    }
+}
+
+impl BatchResults {
+    /// Gets the result of a call, decoding its return data.
+    /// Calls that failed produce [`Error::Revert`] with the revert data.
+    /// Entries from other batches may produce [`Error::AbiDecodingFailed`].
+    pub fn get<T>(&self, entry: BatchEntry<T>) -> Result<T, Error> {
+        let Some(result) = self.results.get(entry.index) else {
+            let err = "batch entry has no result";
+            return Err(alloy_sol_types::Error::custom(err).into());
+        };
+        match result {
+            Ok(data) => Ok((entry.decode)(data)?),
+            Err(data) => Err(Error::Revert(data.clone())),
+        }
+    }
+
+    /// The raw return or revert data of each call.
+    pub fn into_raw(self) -> Vec<Result<Vec<u8>, Vec<u8>>> {
+        self.results
+    }
+
+    /// The number of calls made.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether no calls were made.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Batch, BatchResults, Error, PendingCall};
+    use alloy_primitives::Address;
+
+    #[test]
+    fn test_aggregate3_calldata() {
+        let mut batch = Batch::new();
+        let entry = batch.add_allow_failure(PendingCall {
+            to: Address::repeat_byte(1),
+            calldata: vec![0xaa, 0xbb, 0xcc, 0xdd],
+            decode: |_| Ok(()),
+        });
+        assert_eq!(entry.index, 0);
+        assert_eq!(batch.len(), 1);
+
+        let calldata = batch.aggregate3_calldata();
+        assert_eq!(calldata[..4], [0x82, 0xad, 0x56, 0xcb]);
+
+        let words: Vec<_> = calldata[4..].chunks(32).collect();
+        assert_eq!(words.len(), 8);
+        assert_eq!(words[0][31], 0x20); // offset of the array
+        assert_eq!(words[1][31], 1); // length of the array
+        assert_eq!(words[3][12..], [1; 20]); // target
+        assert_eq!(words[4][31], 1); // allow failure
+        assert_eq!(words[6][31], 4); // length of the calldata
+        assert_eq!(words[7][..4], [0xaa, 0xbb, 0xcc, 0xdd]);
+    }
+
+    #[test]
+    fn test_results() {
+        let mut batch = Batch::new();
+        let call = || PendingCall {
+            to: Address::ZERO,
+            calldata: vec![],
+            decode: |data| Ok(data.len()),
+        };
+        let first = batch.add(call());
+        let second = batch.add(call());
+
+        let results = BatchResults {
+            results: vec![Ok(vec![1, 2])],
+        };
+        assert_eq!(results.get(first), Ok(2));
+        assert!(matches!(
+            results.get(second),
+            Err(Error::AbiDecodingFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Safe interactions with [`ERC-20`] tokens, including those that don't follow the standard.
+//!
+//! Some tokens, like USDT, return nothing from `transfer` and `approve` rather than a `bool`, which
+//! typed calls would fail to decode. The helpers here treat empty return data as success so long as
+//! the token has code, much like OpenZeppelin's [`SafeERC20`].
+//!
+//! ```ignore
+//! use stylus_sdk::call::{erc20, Call};
+//!
+//! erc20::safe_transfer_from(Call::new_in(self), usdt, msg::sender(), contract::address(), amount)?;
+//! ```
+//!
+//! [`ERC-20`]: https://eips.ethereum.org/EIPS/eip-20
+//! [`SafeERC20`]: https://docs.openzeppelin.com/contracts/5.x/api/token/erc20#SafeERC20
+
+use super::{NonPayableCallContext, RawCall};
+use crate::types::AddressVM;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, Panic, PanicKind, SolError, SolValue};
+
+#[cfg(feature = "reentrant")]
+use crate::storage::Storage;
+
+sol! {
+    /// An operation with an ERC-20 token failed.
+    #[allow(missing_docs)]
+    error SafeERC20FailedOperation(address token);
+}
+
+/// Represents the ways an operation on an ERC-20 token may fail.
+#[derive(Debug, PartialEq)]
+pub enum Erc20Error {
+    /// The token has no code, so the call trivially succeeded without doing anything.
+    NoCode(Address),
+    /// Revert data returned by the token.
+    Revert(Vec<u8>),
+    /// The token returned `false` or otherwise malformed data.
+    Failed(Address),
+    /// Increasing the allowance would overflow.
+    AllowanceOverflow,
+}
+
+impl From<Erc20Error> for Vec<u8> {
+    fn from(err: Erc20Error) -> Vec<u8> {
+        match err {
+            Erc20Error::Revert(data) => data,
+            Erc20Error::NoCode(token) | Erc20Error::Failed(token) => {
+                SafeERC20FailedOperation { token }.abi_encode()
+            }
+            Erc20Error::AllowanceOverflow => Panic::from(PanicKind::UnderOverflow).abi_encode(),
+        }
+    }
+}
+
+/// Transfers `value` tokens to `to`, failing if the token reverts or returns `false`.
+pub fn safe_transfer(
+    context: impl NonPayableCallContext,
+    token: Address,
+    to: Address,
+    value: U256,
+) -> Result<(), Erc20Error> {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    let selector = crate::function_selector!("transfer", Address, U256);
+    call_token(
+        context.gas(),
+        token,
+        selector,
+        (to, value).abi_encode_params(),
+    )
+}
+
+/// Transfers `value` tokens from `from` to `to` using the caller's allowance, failing if the token
+/// reverts or returns `false`.
+pub fn safe_transfer_from(
+    context: impl NonPayableCallContext,
+    token: Address,
+    from: Address,
+    to: Address,
+    value: U256,
+) -> Result<(), Erc20Error> {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    let selector = crate::function_selector!("transferFrom", Address, Address, U256);
+    call_token(
+        context.gas(),
+        token,
+        selector,
+        (from, to, value).abi_encode_params(),
+    )
+}
+
+/// Sets the allowance of `spender` to `value`, failing if the token reverts or returns `false`.
+///
+/// Some tokens, like USDT, refuse to change a nonzero allowance to another nonzero value.
+/// For these, see [`force_approve`].
+pub fn safe_approve(
+    context: impl NonPayableCallContext,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Erc20Error> {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    approve(context.gas(), token, spender, value)
+}
+
+/// Sets the allowance of `spender` to `value`. Should the token refuse, the allowance is first
+/// reset to zero, supporting tokens like USDT that forbid changing one nonzero allowance to another.
+///
+/// Note that the supplied gas limit applies to each of the up to three calls made.
+pub fn force_approve(
+    context: impl NonPayableCallContext,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Erc20Error> {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    force_approve_with_gas(context.gas(), token, spender, value)
+}
+
+/// Increases the allowance of `spender` by `value`, as with [`force_approve`].
+///
+/// Note that the supplied gas limit applies to each of the calls made.
+pub fn safe_increase_allowance(
+    context: impl NonPayableCallContext,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Erc20Error> {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    let gas = context.gas();
+    let selector = crate::function_selector!("allowance", Address, Address);
+    let calldata = calldata(
+        selector,
+        (crate::contract::address(), spender).abi_encode_params(),
+    );
+
+    // safety: the storage cache was cleared above
+    #[allow(unused_unsafe)]
+    let returned = unsafe { RawCall::new_static().gas(gas).call(token, &calldata) };
+    let returned = returned.map_err(Erc20Error::Revert)?;
+    let current = U256::abi_decode(&returned, true).map_err(|_| Erc20Error::Failed(token))?;
+
+    let value = current
+        .checked_add(value)
+        .ok_or(Erc20Error::AllowanceOverflow)?;
+    force_approve_with_gas(gas, token, spender, value)
+}
+
+fn force_approve_with_gas(
+    gas: u64,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Erc20Error> {
+    if approve(gas, token, spender, value).is_ok() {
+        return Ok(());
+    }
+    approve(gas, token, spender, U256::ZERO)?;
+    approve(gas, token, spender, value)
+}
+
+fn approve(gas: u64, token: Address, spender: Address, value: U256) -> Result<(), Erc20Error> {
+    let selector = crate::function_selector!("approve", Address, U256);
+    call_token(gas, token, selector, (spender, value).abi_encode_params())
+}
+
+/// Calls the token, expecting it to return either nothing or `true`.
+fn call_token(
+    gas: u64,
+    token: Address,
+    selector: [u8; 4],
+    args: Vec<u8>,
+) -> Result<(), Erc20Error> {
+    let calldata = calldata(selector, args);
+
+    // safety: callers handle the storage cache
+    #[allow(unused_unsafe)]
+    let returned = unsafe { RawCall::new().gas(gas).call(token, &calldata) };
+    let returned = returned.map_err(Erc20Error::Revert)?;
+
+    match returns_success(&returned) {
+        Some(true) if returned.is_empty() && !token.has_code() => Err(Erc20Error::NoCode(token)),
+        Some(true) => Ok(()),
+        _ => Err(Erc20Error::Failed(token)),
+    }
+}
+
+fn calldata(selector: [u8; 4], args: Vec<u8>) -> Vec<u8> {
+    let mut calldata = selector.to_vec();
+    calldata.extend(args);
+    calldata
+}
+
+/// Determines whether a token's return data indicates success, with `None` if it's too short.
+/// As with Solidity, only the first word is considered, which must be exactly `1`.
+fn returns_success(returned: &[u8]) -> Option<bool> {
+    if returned.is_empty() {
+        return Some(true);
+    }
+    let word = returned.get(..32)?;
+    Some(U256::from_be_slice(word) == U256::from(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calldata, returns_success, Erc20Error, SafeERC20FailedOperation};
+    use alloc::vec::Vec;
+    use alloy_primitives::{Address, U256};
+    use alloy_sol_types::{SolError, SolValue};
+
+    #[test]
+    fn test_returns_success() {
+        let mut word = [0; 32];
+        assert_eq!(returns_success(&[]), Some(true));
+        assert_eq!(returns_success(&word), Some(false));
+        word[31] = 1;
+        assert_eq!(returns_success(&word), Some(true));
+        word[31] = 2;
+        assert_eq!(returns_success(&word), Some(false));
+        assert_eq!(returns_success(&[1]), None);
+    }
+
+    #[test]
+    fn test_calldata() {
+        let to = Address::repeat_byte(7);
+        let data = calldata(
+            [0xa9, 0x05, 0x9c, 0xbb],
+            (to, U256::from(5)).abi_encode_params(),
+        );
+        assert_eq!(data.len(), 4 + 64);
+        assert_eq!(data[16..36], [7; 20]);
+        assert_eq!(data[67], 5);
+
+        let token = Address::repeat_byte(1);
+        let revert: Vec<u8> = Erc20Error::Failed(token).into();
+        assert_eq!(revert, SafeERC20FailedOperation { token }.abi_encode());
+    }
+}
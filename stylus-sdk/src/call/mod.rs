@@ -7,7 +7,9 @@
 //! - [`Call`] with [`sol_interface!`][sol_interface] for richly-typed calls.
 //! - [`RawCall`] for `unsafe`, bytes-in bytes-out calls.
 //!
-//! Additional helpers exist for specific use-cases like [`transfer_eth`].
+//! Additional helpers exist for specific use-cases like [`transfer_eth`], [`try_call`] for
+//! calls that are allowed to fail, [`Batch`] for making many calls at once,
+//! and [`erc20`] for safely interacting with tokens.
 //!
 //! [sol_interface]: crate::prelude::sol_interface
 
@@ -15,7 +17,13 @@ use alloc::vec::Vec;
 use alloy_primitives::Address;
 
 pub use self::{
-    context::Call, error::Error, error::MethodError, raw::RawCall, traits::*,
+    batch::{Batch, BatchEntry, BatchResults, PendingCall, MULTICALL3},
+    context::Call,
+    error::Error,
+    error::MethodError,
+    outcome::CallOutcome,
+    raw::RawCall,
+    traits::*,
     transfer::transfer_eth,
 };
 
@@ -24,8 +32,11 @@ pub(crate) use raw::CachePolicy;
 #[cfg(feature = "reentrant")]
 use crate::storage::Storage;
 
+mod batch;
 mod context;
+pub mod erc20;
 mod error;
+mod outcome;
 mod raw;
 mod traits;
 mod transfer;
@@ -92,3 +103,220 @@ pub fn call(context: impl MutatingCallContext, to: Address, data: &[u8]) -> Resu
             .map_err(Error::Revert)
     }}
 }
+
+/// Static calls the contract at the given address, reporting the outcome rather than an error.
+/// See [`RawCall::try_call`] for more information.
+pub fn try_static_call(context: impl StaticCallContext, to: Address, data: &[u8]) -> CallOutcome {
+    #[cfg(feature = "reentrant")]
+    Storage::flush(); // flush storage to persist changes, but don't invalidate the cache
+
+    unsafe_reentrant! {{
+        RawCall::new_static()
+            .gas(context.gas())
+            .try_call(to, data)
+    }}
+}
+
+/// Calls the contract at the given address, reporting the outcome rather than an error.
+/// See [`RawCall::try_call`] for more information.
+pub fn try_call(context: impl MutatingCallContext, to: Address, data: &[u8]) -> CallOutcome {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    unsafe_reentrant! {{
+        RawCall::new_with_value(context.value())
+            .gas(context.gas())
+            .try_call(to, data)
+    }}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{abi::Interface, crypto, prelude::sol_interface};
+    use alloc::vec::Vec;
+    use alloy_primitives::{Address, FixedBytes, B256, U256};
+    use alloy_sol_types::{sol, SolCall, SolError, SolInterface, SolValue};
+
+    sol_interface! {
+        interface IERC20 {
+            function balanceOf(address owner) external view returns (uint256);
+            function transfer(address to, uint256 amount) external returns (bool);
+        }
+
+        interface IERC20Burnable is IERC20 {
+            function burn(uint256 amount) external;
+        }
+    }
+
+    sol_interface! {
+        interface IUniswapV2Pair {
+            function getReserves()
+                external
+                view
+                returns (uint112 reserve0, uint112 reserve1, uint32 timestamp);
+        }
+    }
+
+    sol_interface! {
+        interface IERC1271 {
+            function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4);
+            function supportsInterface(bytes4 id) external view returns (bool);
+        }
+    }
+
+    sol! {
+        function balanceOf(address owner) external view returns (uint256);
+        function transfer(address to, uint256 amount) external returns (bool);
+        function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4);
+        function supportsInterface(bytes4 id) external view returns (bool);
+    }
+
+    mod metadata {
+        use super::IERC20;
+        use crate::prelude::sol_interface;
+
+        sol_interface! {
+            interface IERC20Metadata is IERC20 {
+                function decimals() external view returns (uint8);
+            }
+        }
+    }
+
+    sol_interface! {
+        interface IService {
+            struct Payment { address user; uint256 amount; }
+            enum Kind { Once, Recurring }
+            error Unauthorized(address caller);
+
+            function pay(Payment calldata payment, Kind kind) external;
+        }
+    }
+
+    sol_interface! {
+        interface IERC721 {
+            function safeTransferFrom(address from, address to, uint256 id) external;
+
+            #[rust_name = "safe_transfer_from_with_data"]
+            function safeTransferFrom(address from, address to, uint256 id, bytes data) external;
+
+            function mint(address to) external;
+            function mint(address to, uint256 amount) external;
+        }
+    }
+
+    fn functions<I: Interface>() -> Vec<(&'static str, u32)> {
+        I::FUNCTIONS
+            .iter()
+            .map(|func| (func.signature, func.selector))
+            .collect()
+    }
+
+    #[test]
+    fn test_inheritance() {
+        let balance_of = ("balanceOf(address)", 0x70a08231);
+        let transfer = ("transfer(address,uint256)", 0xa9059cbb);
+        assert_eq!(functions::<IERC20>(), [balance_of, transfer]);
+
+        let burn = ("burn(uint256)", 0x42966c68);
+        assert_eq!(functions::<IERC20Burnable>(), [balance_of, transfer, burn]);
+
+        let decimals = ("decimals()", 0x313ce567);
+        assert_eq!(
+            functions::<metadata::IERC20Metadata>(),
+            [balance_of, transfer, decimals]
+        );
+
+        let calldata = metadata::IERC20Metadata::encode_balance_of(Address::ZERO);
+        assert_eq!(calldata[..4], 0x70a08231_u32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_custom_types() {
+        let signature = "pay((address,uint256),uint8)";
+        let selector = u32::from_be_bytes(crypto::keccak(signature)[..4].try_into().unwrap());
+        assert_eq!(functions::<IService>(), [(signature, selector)]);
+
+        let user = Address::repeat_byte(1);
+        let payment = i_service::Payment {
+            user,
+            amount: U256::from(5),
+        };
+        let calldata = IService::encode_pay(payment, i_service::Kind::Recurring);
+        assert_eq!(calldata[..4], selector.to_be_bytes());
+
+        let (payment, kind) =
+            <(i_service::Payment, i_service::Kind)>::abi_decode_params(&calldata[4..], true)
+                .unwrap();
+        assert_eq!((payment.user, payment.amount), (user, U256::from(5)));
+        assert!(matches!(kind, i_service::Kind::Recurring));
+
+        let revert = i_service::Unauthorized { caller: user }.abi_encode();
+        let err = i_service::IServiceErrors::abi_decode(&revert, true).unwrap();
+        assert!(matches!(err, i_service::IServiceErrors::Unauthorized(e) if e.caller == user));
+    }
+
+    #[test]
+    fn test_overloads() {
+        let (from, to, id) = (Address::ZERO, Address::ZERO, U256::ZERO);
+        let calldata = IERC721::encode_safe_transfer_from(from, to, id);
+        assert_eq!(calldata[..4], 0x42842e0e_u32.to_be_bytes());
+        let data = Vec::new().into();
+        let calldata = IERC721::encode_safe_transfer_from_with_data(from, to, id, data);
+        assert_eq!(calldata[..4], 0xb88d4fde_u32.to_be_bytes());
+
+        let mint_0 = IERC721::encode_mint_0(to);
+        let mint_1 = IERC721::encode_mint_1(to, U256::from(1));
+        assert_eq!(mint_0[..4], crypto::keccak("mint(address)")[..4]);
+        assert_eq!(mint_1[..4], crypto::keccak("mint(address,uint256)")[..4]);
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let (to, amount) = (Address::repeat_byte(2), U256::from(3));
+        let calldata = IERC20::encode_transfer(to, amount);
+        assert_eq!(calldata, transferCall { to, amount }.abi_encode());
+
+        let calldata = IERC20::encode_balance_of(to);
+        assert_eq!(calldata, balanceOfCall { owner: to }.abi_encode());
+
+        let returned = transferCall::abi_encode_returns(&(true,));
+        assert!(IERC20::decode_transfer_return(&returned).unwrap());
+
+        let returned = balanceOfCall::abi_encode_returns(&(amount,));
+        assert_eq!(IERC20::decode_balance_of_return(&returned).unwrap(), amount);
+        assert!(IERC20::decode_balance_of_return(&returned[..31]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_bytes() {
+        let is_valid = ("isValidSignature(bytes32,bytes)", 0x1626ba7e);
+        let supports = ("supportsInterface(bytes4)", 0x01ffc9a7);
+        assert_eq!(functions::<IERC1271>(), [is_valid, supports]);
+        assert_eq!(isValidSignatureCall::SELECTOR, 0x1626ba7e_u32.to_be_bytes());
+        assert_eq!(supportsInterfaceCall::SELECTOR, 0x01ffc9a7_u32.to_be_bytes());
+
+        let (hash, id) = (B256::repeat_byte(1), FixedBytes([1, 2, 3, 4]));
+        let calldata = IERC1271::encode_is_valid_signature(hash, vec![5].into());
+        let signature = vec![5].into();
+        assert_eq!(calldata, isValidSignatureCall { hash, signature }.abi_encode());
+
+        let calldata = IERC1271::encode_supports_interface(id);
+        assert_eq!(calldata, supportsInterfaceCall { id }.abi_encode());
+
+        let returned = isValidSignatureCall::abi_encode_returns(&(id,));
+        assert_eq!(IERC1271::decode_is_valid_signature_return(&returned).unwrap(), id);
+    }
+
+    #[test]
+    fn test_named_returns() {
+        let returned = (U256::from(1), U256::from(2), 3_u32).abi_encode_params();
+        let reserves = IUniswapV2Pair::decode_get_reserves_return(&returned).unwrap();
+        let reserves: i_uniswap_v_2_pair::GetReservesReturn = reserves;
+        assert_eq!(reserves.reserve0, 1);
+        assert_eq!(reserves.reserve1, 2);
+        assert_eq!(reserves.timestamp, 3);
+
+        let signature = ("getReserves()", 0x0902f1ac);
+        assert_eq!(functions::<IUniswapV2Pair>(), [signature]);
+    }
+}
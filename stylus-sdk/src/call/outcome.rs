@@ -0,0 +1,51 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::Error;
+use alloc::vec::Vec;
+
+/// The outcome of a call that was allowed to fail, as with [`RawCall::try_call`].
+///
+/// Unlike the errors of other calls, this includes everything needed to implement Solidity-style
+/// `try`/`catch`, such as falling back to another strategy when the callee reverts.
+///
+/// [`RawCall::try_call`]: super::RawCall::try_call
+#[derive(Debug, PartialEq)]
+#[must_use]
+pub struct CallOutcome<T = Vec<u8>> {
+    /// Whether the callee succeeded, rather than reverting.
+    pub success: bool,
+    /// The return value if the callee succeeded, or else its revert data.
+    /// Typed calls that fail to decode the return data produce [`Error::AbiDecodingFailed`].
+    pub result: Result<T, Error>,
+    /// The length of the callee's return data, which may exceed the amount copied.
+    pub return_data_len: usize,
+    /// The amount of gas consumed by the call.
+    pub gas_used: u64,
+}
+
+impl CallOutcome {
+    /// Decodes the return data of a successful call.
+    pub fn decode<T>(
+        self,
+        decode: impl FnOnce(&[u8]) -> Result<T, alloy_sol_types::Error>,
+    ) -> CallOutcome<T> {
+        let result = match self.result {
+            Ok(data) => decode(&data).map_err(Error::AbiDecodingFailed),
+            Err(err) => Err(err),
+        };
+        CallOutcome {
+            success: self.success,
+            result,
+            return_data_len: self.return_data_len,
+            gas_used: self.gas_used,
+        }
+    }
+}
+
+impl<T> CallOutcome<T> {
+    /// Converts the outcome into its result, discarding everything else.
+    pub fn into_result(self) -> Result<T, Error> {
+        self.result
+    }
+}
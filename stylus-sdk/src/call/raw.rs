@@ -1,9 +1,10 @@
 // Copyright 2023-2024, Offchain Labs, Inc.
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
+use super::{CallOutcome, Error};
 use crate::{
-    contract::{read_return_data, RETURN_DATA_LEN},
-    hostio, tx, ArbResult,
+    contract::{self, read_return_data, RETURN_DATA_LEN},
+    evm, hostio, tx, ArbResult,
 };
 use alloy_primitives::{Address, B256, U256};
 use cfg_if::cfg_if;
@@ -235,4 +236,39 @@ impl RawCall {
             }
         }
     }
+
+    unsafe_reentrant! {
+        /// Performs a raw call like [`call`], but reports the outcome in detail.
+        /// This includes the amount of gas used and the full length of the return data.
+        ///
+        /// ```no_run
+        /// use stylus_sdk::call::RawCall;
+        /// use stylus_sdk::alloy_primitives::address;
+        ///
+        /// let keeper = address!("361594F5429D23ECE0A88E4fBE529E1c49D524d8");
+        ///
+        /// # #[allow(unused_unsafe)]
+        /// let outcome = unsafe { RawCall::new().gas(100_000).try_call(keeper, &[]) };
+        /// if !outcome.success {
+        ///     // fall back to another strategy
+        /// }
+        /// ```
+        ///
+        /// # Safety
+        ///
+        /// This function becomes `unsafe` when the `reentrant` feature is enabled, for the same
+        /// reasons as [`call`].
+        ///
+        /// [`call`]: RawCall::call
+        pub fn try_call(self, contract: Address, calldata: &[u8]) -> CallOutcome {
+            let gas_left = evm::gas_left();
+            let result = self.call(contract, calldata).map_err(Error::Revert);
+            CallOutcome {
+                success: result.is_ok(),
+                result,
+                return_data_len: contract::return_data_len(),
+                gas_used: gas_left.saturating_sub(evm::gas_left()),
+            }
+        }
+    }
 }
@@ -0,0 +1,311 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Operations on the [`BN254`] curve, also known as `alt_bn128`, for verifying SNARKs.
+//!
+//! Points are added, multiplied, and paired using the precompiles of [`EIP-196`] and [`EIP-197`].
+//! The points and [`Groth16`] types implement [`AbiType`], so verifying keys and proofs may be
+//! accepted directly by `#[public]` methods with the same ABI as their Solidity counterparts.
+//!
+//! ```no_run
+//! use stylus_sdk::crypto::bn254::{self, Groth16Proof, Groth16VerifyingKey};
+//! use stylus_sdk::alloy_primitives::U256;
+//!
+//! fn verify(vk: &Groth16VerifyingKey, proof: &Groth16Proof, inputs: &[U256]) -> bool {
+//!     bn254::verify_groth16(vk, proof, inputs)
+//! }
+//! ```
+//!
+//! [`BN254`]: https://hackmd.io/@jpw/bn254
+//! [`EIP-196`]: https://eips.ethereum.org/EIPS/eip-196
+//! [`EIP-197`]: https://eips.ethereum.org/EIPS/eip-197
+//! [`Groth16`]: https://eprint.iacr.org/2016/260.pdf
+
+use super::precompiles::static_call_precompile;
+use crate::abi::{AbiType, ConstString};
+use alloc::vec::Vec;
+use alloy_primitives::{uint, U256};
+use alloy_sol_types::sol;
+
+/// The modulus of the field over which the curve is defined.
+pub const BASE_FIELD_MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088696311157297823662689037894645226208583_U256);
+
+/// The order of the curve's groups, which bounds the scalars of [`scalar_mul`].
+pub const SCALAR_FIELD_MODULUS: U256 =
+    uint!(21888242871839275222246405745257275088548364400416034343698204186575808495617_U256);
+
+sol! {
+    /// A point on the curve over the base field, with `(0, 0)` representing the point at infinity.
+    #[derive(Copy, Debug, Default, PartialEq, Eq)]
+    struct G1Point {
+        /// The x coordinate.
+        uint256 x;
+        /// The y coordinate.
+        uint256 y;
+    }
+
+    /// A point on the twisted curve over the quadratic extension field.
+    ///
+    /// As with the pairing precompile, each coordinate lists its imaginary part first.
+    #[derive(Copy, Debug, Default, PartialEq, Eq)]
+    struct G2Point {
+        /// The x coordinate, imaginary part first.
+        uint256[2] x;
+        /// The y coordinate, imaginary part first.
+        uint256[2] y;
+    }
+
+    /// The verifying key of a [`Groth16`] circuit.
+    ///
+    /// [`Groth16`]: https://eprint.iacr.org/2016/260.pdf
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct Groth16VerifyingKey {
+        /// The `α` point.
+        G1Point alpha;
+        /// The `β` point.
+        G2Point beta;
+        /// The `γ` point.
+        G2Point gamma;
+        /// The `δ` point.
+        G2Point delta;
+        /// The points for the public inputs, of which there's one more than there are inputs.
+        G1Point[] ic;
+    }
+
+    /// A [`Groth16`] proof.
+    ///
+    /// [`Groth16`]: https://eprint.iacr.org/2016/260.pdf
+    #[derive(Copy, Debug, Default, PartialEq, Eq)]
+    struct Groth16Proof {
+        /// The `A` point.
+        G1Point a;
+        /// The `B` point.
+        G2Point b;
+        /// The `C` point.
+        G1Point c;
+    }
+}
+
+const G1_ABI: &str = "(uint256,uint256)";
+const G2_ABI: &str = "(uint256[2],uint256[2])";
+
+impl AbiType for G1Point {
+    type SolType = Self;
+
+    const ABI: ConstString = ConstString::new(G1_ABI);
+}
+
+impl AbiType for G2Point {
+    type SolType = Self;
+
+    const ABI: ConstString = ConstString::new(G2_ABI);
+}
+
+impl AbiType for Groth16VerifyingKey {
+    type SolType = Self;
+
+    const ABI: ConstString = ConstString::new("(")
+        .concat(ConstString::new(G1_ABI))
+        .concat(ConstString::new(","))
+        .concat(ConstString::new(G2_ABI))
+        .concat(ConstString::new(","))
+        .concat(ConstString::new(G2_ABI))
+        .concat(ConstString::new(","))
+        .concat(ConstString::new(G2_ABI))
+        .concat(ConstString::new(","))
+        .concat(ConstString::new(G1_ABI))
+        .concat(ConstString::new("[])"));
+
+    const EXPORT_ABI_ARG: ConstString = Self::ABI.concat(ConstString::new(" memory"));
+
+    const EXPORT_ABI_RET: ConstString = Self::EXPORT_ABI_ARG;
+
+    const CAN_BE_CALLDATA: bool = false;
+}
+
+impl AbiType for Groth16Proof {
+    type SolType = Self;
+
+    const ABI: ConstString = ConstString::new("(")
+        .concat(ConstString::new(G1_ABI))
+        .concat(ConstString::new(","))
+        .concat(ConstString::new(G2_ABI))
+        .concat(ConstString::new(","))
+        .concat(ConstString::new(G1_ABI))
+        .concat(ConstString::new(")"));
+}
+
+impl G1Point {
+    /// Creates a point from its coordinates.
+    pub const fn new(x: U256, y: U256) -> Self {
+        Self { x, y }
+    }
+
+    /// The generator of the group.
+    pub const fn generator() -> Self {
+        Self::new(uint!(1_U256), uint!(2_U256))
+    }
+
+    /// Whether this is the point at infinity.
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero()
+    }
+
+    /// The additive inverse of the point.
+    pub fn negate(&self) -> Self {
+        if self.is_infinity() {
+            return *self;
+        }
+        let y = BASE_FIELD_MODULUS - self.y % BASE_FIELD_MODULUS;
+        Self::new(self.x, y % BASE_FIELD_MODULUS)
+    }
+
+    fn to_bytes(self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.x.to_be_bytes::<32>());
+        bytes[32..].copy_from_slice(&self.y.to_be_bytes::<32>());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..64)?;
+        let x = U256::from_be_slice(&bytes[..32]);
+        let y = U256::from_be_slice(&bytes[32..]);
+        Some(Self::new(x, y))
+    }
+}
+
+impl G2Point {
+    /// Creates a point from its coordinates, each listing its imaginary part first.
+    pub const fn new(x: [U256; 2], y: [U256; 2]) -> Self {
+        Self { x, y }
+    }
+
+    fn to_bytes(self) -> [u8; 128] {
+        let mut bytes = [0; 128];
+        let words = [self.x[0], self.x[1], self.y[0], self.y[1]];
+        for (chunk, word) in bytes.chunks_mut(32).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes::<32>());
+        }
+        bytes
+    }
+}
+
+/// Adds two points via the `ecAdd` precompile, returning `None` if either isn't on the curve.
+pub fn add(a: G1Point, b: G1Point) -> Option<G1Point> {
+    let mut input = [0; 128];
+    input[..64].copy_from_slice(&a.to_bytes());
+    input[64..].copy_from_slice(&b.to_bytes());
+    let output = static_call_precompile(0x06, &input).ok()?;
+    G1Point::from_bytes(&output)
+}
+
+/// Multiplies a point by a scalar via the `ecMul` precompile, returning `None` if the point isn't
+/// on the curve.
+pub fn scalar_mul(point: G1Point, scalar: U256) -> Option<G1Point> {
+    let mut input = [0; 96];
+    input[..64].copy_from_slice(&point.to_bytes());
+    input[64..].copy_from_slice(&scalar.to_be_bytes::<32>());
+    let output = static_call_precompile(0x07, &input).ok()?;
+    G1Point::from_bytes(&output)
+}
+
+/// Checks via the `ecPairing` precompile that the product of the pairings of each pair of points
+/// is the identity. Returns `None` if any of the points are invalid.
+pub fn pairing_check(pairs: &[(G1Point, G2Point)]) -> Option<bool> {
+    let output = static_call_precompile(0x08, &pairing_input(pairs)).ok()?;
+    let word = output.get(..32)?;
+    Some(U256::from_be_slice(word) == U256::from(1))
+}
+
+fn pairing_input(pairs: &[(G1Point, G2Point)]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(pairs.len() * 192);
+    for (g1, g2) in pairs {
+        input.extend(g1.to_bytes());
+        input.extend(g2.to_bytes());
+    }
+    input
+}
+
+/// Verifies a [`Groth16`] proof against a verifying key and the circuit's public inputs, as done
+/// by the Solidity verifiers of tools like [`snarkjs`].
+///
+/// Returns `false` if the proof is invalid, the number of inputs doesn't match the verifying key,
+/// any input exceeds the [`SCALAR_FIELD_MODULUS`], or any of the points aren't on the curve.
+///
+/// [`Groth16`]: https://eprint.iacr.org/2016/260.pdf
+/// [`snarkjs`]: https://github.com/iden3/snarkjs
+pub fn verify_groth16(vk: &Groth16VerifyingKey, proof: &Groth16Proof, inputs: &[U256]) -> bool {
+    let Some((first, ic)) = vk.ic.split_first() else {
+        return false;
+    };
+    if ic.len() != inputs.len() {
+        return false;
+    }
+
+    let mut vk_x = *first;
+    for (point, input) in ic.iter().zip(inputs) {
+        if *input >= SCALAR_FIELD_MODULUS {
+            return false;
+        }
+        let Some(term) = scalar_mul(*point, *input) else {
+            return false;
+        };
+        let Some(sum) = add(vk_x, term) else {
+            return false;
+        };
+        vk_x = sum;
+    }
+
+    let pairs = [
+        (proof.a.negate(), proof.b),
+        (vk.alpha, vk.beta),
+        (vk_x, vk.gamma),
+        (proof.c, vk.delta),
+    ];
+    pairing_check(&pairs).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        pairing_input, G1Point, G2Point, Groth16Proof, Groth16VerifyingKey, BASE_FIELD_MODULUS,
+    };
+    use crate::abi::AbiType;
+    use alloy_primitives::U256;
+
+    #[test]
+    fn test_points() {
+        let g = G1Point::generator();
+        let neg = g.negate();
+        assert_eq!(neg.x, g.x);
+        assert_eq!(neg.y, BASE_FIELD_MODULUS - U256::from(2));
+        assert_eq!(neg.negate(), g);
+        assert_eq!(G1Point::default().negate(), G1Point::default());
+        assert_eq!(G1Point::from_bytes(&g.to_bytes()), Some(g));
+
+        let g2 = G2Point::new(
+            [U256::from(1), U256::from(2)],
+            [U256::from(3), U256::from(4)],
+        );
+        let input = pairing_input(&[(g, g2)]);
+        assert_eq!(input.len(), 192);
+        let words: alloc::vec::Vec<_> = input.chunks(32).map(|w| w[31]).collect();
+        assert_eq!(words, [1, 2, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_abi() {
+        assert_eq!(G1Point::ABI.as_str(), "(uint256,uint256)");
+        assert_eq!(
+            Groth16Proof::ABI.as_str(),
+            "((uint256,uint256),(uint256[2],uint256[2]),(uint256,uint256))"
+        );
+        assert_eq!(
+            Groth16VerifyingKey::EXPORT_ABI_ARG.as_str(),
+            "((uint256,uint256),(uint256[2],uint256[2]),(uint256[2],uint256[2]),\
+             (uint256[2],uint256[2]),(uint256,uint256)[]) memory"
+        );
+    }
+}
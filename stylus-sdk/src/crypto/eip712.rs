@@ -0,0 +1,423 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Hashing of typed structured data, as specified by [`EIP-712`].
+//!
+//! Structs implement [`Eip712`] via `#[derive(Eip712)]`, which provides their type string, typehash,
+//! and struct hash. A [`Domain`] then combines a struct hash into the digest that gets signed.
+//!
+//! ```no_run
+//! use stylus_sdk::crypto::{self, eip712::{Domain, Eip712}};
+//! use stylus_sdk::alloy_primitives::{Address, B256, U256};
+//! use stylus_sdk::prelude::*;
+//! extern crate alloc;
+//!
+//! #[derive(Eip712)]
+//! struct Permit {
+//!     owner: Address,
+//!     spender: Address,
+//!     value: U256,
+//!     nonce: U256,
+//!     deadline: U256,
+//! }
+//!
+//! fn verify(permit: &Permit, v: u8, r: B256, s: B256) -> bool {
+//!     let digest = Domain::new("MyToken", "1").hash_typed_data(permit);
+//!     crypto::ecrecover(digest, v, r, s) == Some(permit.owner)
+//! }
+//! ```
+//!
+//! Structs declared with [`sol!`] already implement [`SolStruct`], whose
+//! [`eip712_hash_struct`] may be passed to [`Domain::hash_struct`].
+//!
+//! [`EIP-712`]: https://eips.ethereum.org/EIPS/eip-712
+//! [`sol!`]: alloy_sol_types::sol
+//! [`SolStruct`]: alloy_sol_types::SolStruct
+//! [`eip712_hash_struct`]: alloy_sol_types::SolStruct::eip712_hash_struct
+
+use super::{keccak, Keccak};
+use crate::abi::Bytes;
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+use alloy_primitives::{Address, FixedBytes, Signed, Uint, B256, U256};
+
+/// A struct that may be hashed according to [`EIP-712`].
+/// This is typically implemented via `#[derive(Eip712)]`.
+///
+/// [`EIP-712`]: https://eips.ethereum.org/EIPS/eip-712
+pub trait Eip712 {
+    /// The name of the struct.
+    const NAME: &'static str;
+
+    /// The struct's name and members, like `Mail(address from,address to,string contents)`.
+    fn root_type() -> String;
+
+    /// Adds the root types of the structs referenced by this one, recursively.
+    fn components(out: &mut Vec<String>);
+
+    /// The encoded type, which is the root type followed by those of the referenced structs,
+    /// sorted by name.
+    fn encode_type() -> String {
+        let mut components = Vec::new();
+        Self::components(&mut components);
+        components.sort();
+        components.dedup();
+
+        let mut encoded = Self::root_type();
+        for component in components {
+            encoded += &component;
+        }
+        encoded
+    }
+
+    /// The hash of the encoded type.
+    fn type_hash() -> B256 {
+        keccak(Self::encode_type())
+    }
+
+    /// Encodes each of the members as a 32-byte word.
+    fn encode_data(&self, hasher: &mut Keccak);
+
+    /// The hash of the struct, which is signed via [`Domain::hash_struct`].
+    fn struct_hash(&self) -> B256 {
+        let mut hasher = Keccak::new();
+        hasher.update(Self::type_hash());
+        self.encode_data(&mut hasher);
+        hasher.finalize()
+    }
+}
+
+/// A type that may be the member of an [`Eip712`] struct.
+pub trait Eip712Type {
+    /// The name of the type, like `uint256` or `Mail[]`.
+    fn type_name() -> Cow<'static, str>;
+
+    /// Adds the root types of any structs this type references.
+    fn components(_out: &mut Vec<String>) {}
+
+    /// Encodes the value as a 32-byte word.
+    fn encode_value(&self) -> B256;
+}
+
+impl<T: Eip712> Eip712Type for T {
+    fn type_name() -> Cow<'static, str> {
+        T::NAME.into()
+    }
+
+    fn components(out: &mut Vec<String>) {
+        out.push(T::root_type());
+        T::components(out);
+    }
+
+    fn encode_value(&self) -> B256 {
+        self.struct_hash()
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Eip712Type for Uint<BITS, LIMBS> {
+    fn type_name() -> Cow<'static, str> {
+        format!("uint{BITS}").into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        U256::from(*self).into()
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Eip712Type for Signed<BITS, LIMBS> {
+    fn type_name() -> Cow<'static, str> {
+        format!("int{BITS}").into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        let raw = U256::from(self.into_raw());
+        match self.is_negative() {
+            true => B256::from(raw | (U256::MAX << BITS)),
+            false => B256::from(raw),
+        }
+    }
+}
+
+macro_rules! impl_int {
+    ($($uint:ident $int:ident $bits:literal)+) => {
+        $(
+            impl Eip712Type for $uint {
+                fn type_name() -> Cow<'static, str> {
+                    concat!("uint", $bits).into()
+                }
+
+                fn encode_value(&self) -> B256 {
+                    U256::from(*self).into()
+                }
+            }
+
+            impl Eip712Type for $int {
+                fn type_name() -> Cow<'static, str> {
+                    concat!("int", $bits).into()
+                }
+
+                fn encode_value(&self) -> B256 {
+                    let raw = U256::from(*self as $uint);
+                    match self.is_negative() {
+                        true => B256::from(raw | (U256::MAX << $bits as usize)),
+                        false => B256::from(raw),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_int!(u8 i8 8 u16 i16 16 u32 i32 32 u64 i64 64 u128 i128 128);
+
+impl Eip712Type for bool {
+    fn type_name() -> Cow<'static, str> {
+        "bool".into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        U256::from(*self as u8).into()
+    }
+}
+
+impl Eip712Type for Address {
+    fn type_name() -> Cow<'static, str> {
+        "address".into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        self.into_word()
+    }
+}
+
+impl<const N: usize> Eip712Type for FixedBytes<N> {
+    fn type_name() -> Cow<'static, str> {
+        format!("bytes{N}").into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        let mut word = B256::ZERO;
+        word[..N].copy_from_slice(self.as_slice());
+        word
+    }
+}
+
+impl Eip712Type for String {
+    fn type_name() -> Cow<'static, str> {
+        "string".into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        keccak(self)
+    }
+}
+
+impl Eip712Type for Bytes {
+    fn type_name() -> Cow<'static, str> {
+        "bytes".into()
+    }
+
+    fn encode_value(&self) -> B256 {
+        keccak(&self.0)
+    }
+}
+
+impl<T: Eip712Type> Eip712Type for Vec<T> {
+    fn type_name() -> Cow<'static, str> {
+        format!("{}[]", T::type_name()).into()
+    }
+
+    fn components(out: &mut Vec<String>) {
+        T::components(out);
+    }
+
+    fn encode_value(&self) -> B256 {
+        encode_array(self)
+    }
+}
+
+impl<T: Eip712Type, const N: usize> Eip712Type for [T; N] {
+    fn type_name() -> Cow<'static, str> {
+        format!("{}[{N}]", T::type_name()).into()
+    }
+
+    fn components(out: &mut Vec<String>) {
+        T::components(out);
+    }
+
+    fn encode_value(&self) -> B256 {
+        encode_array(self)
+    }
+}
+
+/// Arrays are encoded as the hash of the concatenated encodings of their elements.
+fn encode_array<T: Eip712Type>(items: &[T]) -> B256 {
+    let mut hasher = Keccak::with_capacity(items.len() * 32);
+    for item in items {
+        hasher.update(item.encode_value());
+    }
+    hasher.finalize()
+}
+
+/// The domain separating the signatures of one contract or version from those of another.
+///
+/// Each member is optional, and only those that are set are included in the separator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Domain {
+    /// The name of the signing domain, such as that of the dApp.
+    pub name: Option<String>,
+    /// The current major version of the signing domain.
+    pub version: Option<String>,
+    /// The chain the signatures are valid on.
+    pub chain_id: Option<U256>,
+    /// The contract that will verify the signatures.
+    pub verifying_contract: Option<Address>,
+    /// A salt for disambiguating otherwise identical domains.
+    pub salt: Option<B256>,
+}
+
+impl Domain {
+    /// Creates a domain for the current contract on the current chain.
+    pub fn new(name: &str, version: &str) -> Self {
+        Self {
+            name: Some(name.into()),
+            version: Some(version.into()),
+            chain_id: Some(U256::from(crate::block::chainid())),
+            verifying_contract: Some(crate::contract::address()),
+            salt: None,
+        }
+    }
+
+    /// The hash of the domain, which is the struct hash of an `EIP712Domain` struct.
+    pub fn separator(&self) -> B256 {
+        let mut members = Vec::new();
+        let mut words = Vec::new();
+        if let Some(name) = &self.name {
+            members.push("string name");
+            words.push(keccak(name));
+        }
+        if let Some(version) = &self.version {
+            members.push("string version");
+            words.push(keccak(version));
+        }
+        if let Some(chain_id) = self.chain_id {
+            members.push("uint256 chainId");
+            words.push(chain_id.into());
+        }
+        if let Some(contract) = self.verifying_contract {
+            members.push("address verifyingContract");
+            words.push(contract.into_word());
+        }
+        if let Some(salt) = self.salt {
+            members.push("bytes32 salt");
+            words.push(salt);
+        }
+
+        let mut hasher = Keccak::with_capacity(32 * (words.len() + 1));
+        hasher.update(keccak(format!("EIP712Domain({})", members.join(","))));
+        for word in words {
+            hasher.update(word);
+        }
+        hasher.finalize()
+    }
+
+    /// Combines a struct hash with the domain, producing the digest that gets signed.
+    pub fn hash_struct(&self, struct_hash: B256) -> B256 {
+        let mut hasher = Keccak::with_capacity(66);
+        hasher.update([0x19, 0x01]);
+        hasher.update(self.separator());
+        hasher.update(struct_hash);
+        hasher.finalize()
+    }
+
+    /// Computes the digest of a typed struct, which gets signed.
+    pub fn hash_typed_data<T: Eip712>(&self, value: &T) -> B256 {
+        self.hash_struct(value.struct_hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Domain, Eip712, Eip712Type};
+    use crate::prelude::Eip712;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use alloy_primitives::{address, b256, Address, U256};
+
+    // the example from the EIP
+    #[derive(Eip712)]
+    struct Person {
+        name: String,
+        wallet: Address,
+    }
+
+    #[derive(Eip712)]
+    struct Mail {
+        from: Person,
+        to: Person,
+        contents: String,
+    }
+
+    #[test]
+    fn test_eip712() {
+        let mail = Mail {
+            from: Person {
+                name: "Cow".into(),
+                wallet: address!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+            },
+            to: Person {
+                name: "Bob".into(),
+                wallet: address!("bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            },
+            contents: "Hello, Bob!".into(),
+        };
+        let domain = Domain {
+            name: Some("Ether Mail".into()),
+            version: Some("1".into()),
+            chain_id: Some(U256::from(1)),
+            verifying_contract: Some(address!("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")),
+            salt: None,
+        };
+
+        assert_eq!(
+            Mail::encode_type(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            Mail::type_hash(),
+            b256!("a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2")
+        );
+        assert_eq!(
+            mail.struct_hash(),
+            b256!("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+        );
+        assert_eq!(
+            domain.separator(),
+            b256!("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+        assert_eq!(
+            domain.hash_typed_data(&mail),
+            b256!("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+    }
+
+    #[test]
+    fn test_eip712_types() {
+        assert_eq!(<Vec<[u8; 3]> as Eip712Type>::type_name(), "uint8[3][]");
+        assert_eq!(
+            <alloy_primitives::I256 as Eip712Type>::type_name(),
+            "int256"
+        );
+        assert_eq!(
+            (-1_i8).encode_value(),
+            alloy_primitives::B256::repeat_byte(0xff)
+        );
+        assert_eq!(
+            alloy_primitives::I128::MINUS_ONE.encode_value(),
+            alloy_primitives::B256::repeat_byte(0xff)
+        );
+        assert_eq!(
+            alloy_primitives::FixedBytes([1, 2]).encode_value()[..3],
+            [1, 2, 0]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Verification of Merkle proofs compatible with OpenZeppelin's [`MerkleProof`] library.
+//!
+//! Pairs of nodes are sorted before being hashed, so proofs needn't say which side each sibling is
+//! on. The [`MerkleTree`] helper builds trees and proofs the same way as OpenZeppelin's
+//! [`StandardMerkleTree`], which is useful for testing contracts natively.
+//!
+//! ```no_run
+//! use stylus_sdk::crypto::merkle;
+//! use stylus_sdk::alloy_primitives::{Address, B256, U256};
+//! use stylus_sdk::alloy_sol_types::SolValue;
+//!
+//! fn can_claim(root: B256, proof: &[B256], account: Address, amount: U256) -> bool {
+//!     let leaf = merkle::leaf_hash(&(account, amount).abi_encode());
+//!     merkle::verify(proof, root, leaf)
+//! }
+//! ```
+//!
+//! [`MerkleProof`]: https://docs.openzeppelin.com/contracts/5.x/api/utils#MerkleProof
+//! [`StandardMerkleTree`]: https://github.com/OpenZeppelin/merkle-tree
+
+use super::{keccak, Keccak};
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+
+/// Hashes an ABI-encoded leaf the way [`StandardMerkleTree`] does, which hashes twice to prevent
+/// second preimage attacks.
+///
+/// [`StandardMerkleTree`]: https://github.com/OpenZeppelin/merkle-tree
+pub fn leaf_hash(encoded: &[u8]) -> B256 {
+    keccak(keccak(encoded))
+}
+
+/// Hashes a pair of nodes after sorting them.
+pub fn hash_pair(a: B256, b: B256) -> B256 {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    let mut hasher = Keccak::with_capacity(64);
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize()
+}
+
+/// Checks that a leaf belongs to the tree with the given root.
+pub fn verify(proof: &[B256], root: B256, leaf: B256) -> bool {
+    process_proof(proof, leaf) == root
+}
+
+/// Computes the root of the tree a leaf would belong to given its proof.
+pub fn process_proof(proof: &[B256], leaf: B256) -> B256 {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(node, *sibling))
+}
+
+/// Checks that each of the leaves belong to the tree with the given root.
+///
+/// See [`process_multiproof`] for the meaning of the arguments. Note that, as with OpenZeppelin's
+/// implementation, leaves must be given in the order [`MerkleTree::multiproof`] produces them.
+pub fn verify_multiproof(
+    proof: &[B256],
+    proof_flags: &[bool],
+    root: B256,
+    leaves: &[B256],
+) -> bool {
+    process_multiproof(proof, proof_flags, leaves) == Some(root)
+}
+
+/// Computes the root of the tree the leaves would belong to given a multiproof.
+///
+/// Each flag says whether the corresponding step hashes two already-known nodes, or instead a
+/// known node and the next element of `proof`. Returns `None` if the multiproof is malformed.
+pub fn process_multiproof(proof: &[B256], proof_flags: &[bool], leaves: &[B256]) -> Option<B256> {
+    if leaves.len() + proof.len() != proof_flags.len() + 1 {
+        return None;
+    }
+
+    let mut hashes = Vec::with_capacity(proof_flags.len());
+    let mut leaves = leaves.iter();
+    let mut proof = proof.iter();
+    let mut hash_pos = 0;
+
+    let mut next_known = |hashes: &Vec<B256>| match leaves.next() {
+        Some(leaf) => Some(*leaf),
+        None => {
+            let hash = hashes.get(hash_pos).copied();
+            hash_pos += 1;
+            hash
+        }
+    };
+
+    for flag in proof_flags {
+        let a = next_known(&hashes)?;
+        let b = match flag {
+            true => next_known(&hashes)?,
+            false => *proof.next()?,
+        };
+        hashes.push(hash_pair(a, b));
+    }
+
+    if let Some(root) = hashes.last() {
+        return proof.next().is_none().then_some(*root);
+    }
+    next_known(&hashes).or_else(|| proof.next().copied())
+}
+
+/// A multiproof for several leaves, as produced by [`MerkleTree::multiproof`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Multiproof {
+    /// The leaves being proven, in the order [`verify_multiproof`] expects.
+    pub leaves: Vec<B256>,
+    /// The sibling nodes needed to compute the root.
+    pub proof: Vec<B256>,
+    /// Flags saying how to combine the leaves and proof.
+    pub proof_flags: Vec<bool>,
+}
+
+/// A Merkle tree laid out like OpenZeppelin's [`StandardMerkleTree`], for generating proofs.
+///
+/// This is intended for off-chain use, such as in tests, rather than in contracts.
+///
+/// ```
+/// use stylus_sdk::crypto::merkle::{self, MerkleTree};
+/// use stylus_sdk::alloy_primitives::B256;
+///
+/// let leaves: Vec<_> = (0..5_u8).map(|i| merkle::leaf_hash(&[i])).collect();
+/// let tree = MerkleTree::new(leaves.clone());
+///
+/// let proof = tree.proof(leaves[3]).unwrap();
+/// assert!(merkle::verify(&proof, tree.root(), leaves[3]));
+///
+/// let multi = tree.multiproof(&leaves[1..4]).unwrap();
+/// assert!(merkle::verify_multiproof(&multi.proof, &multi.proof_flags, tree.root(), &multi.leaves));
+/// ```
+///
+/// [`StandardMerkleTree`]: https://github.com/OpenZeppelin/merkle-tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    nodes: Vec<B256>,
+}
+
+impl MerkleTree {
+    /// Builds a tree from hashed leaves, which are sorted first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no leaves.
+    pub fn new(mut leaves: Vec<B256>) -> Self {
+        assert!(!leaves.is_empty(), "expected at least one leaf");
+        leaves.sort();
+
+        let len = 2 * leaves.len() - 1;
+        let mut nodes = alloc::vec![B256::ZERO; len];
+        for (i, leaf) in leaves.into_iter().enumerate() {
+            nodes[len - 1 - i] = leaf;
+        }
+        for i in (0..len / 2).rev() {
+            nodes[i] = hash_pair(nodes[2 * i + 1], nodes[2 * i + 2]);
+        }
+        Self { nodes }
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> B256 {
+        self.nodes[0]
+    }
+
+    /// Produces the proof for a leaf, if it's in the tree.
+    pub fn proof(&self, leaf: B256) -> Option<Vec<B256>> {
+        let mut index = self.index_of(leaf)?;
+        let mut proof = Vec::new();
+        while index > 0 {
+            proof.push(self.nodes[sibling(index)]);
+            index = parent(index);
+        }
+        Some(proof)
+    }
+
+    /// Produces a multiproof for several leaves, if they're all in the tree.
+    pub fn multiproof(&self, leaves: &[B256]) -> Option<Multiproof> {
+        let mut indices = leaves
+            .iter()
+            .map(|leaf| self.index_of(*leaf))
+            .collect::<Option<Vec<_>>>()?;
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        indices.dedup();
+
+        let mut stack: alloc::collections::VecDeque<_> = indices.iter().copied().collect();
+        let mut proof = Vec::new();
+        let mut proof_flags = Vec::new();
+        while let Some(&index) = stack.front() {
+            if index == 0 {
+                break;
+            }
+            stack.pop_front();
+            let sibling = sibling(index);
+            if stack.front() == Some(&sibling) {
+                proof_flags.push(true);
+                stack.pop_front();
+            } else {
+                proof_flags.push(false);
+                proof.push(self.nodes[sibling]);
+            }
+            stack.push_back(parent(index));
+        }
+        if indices.is_empty() {
+            proof.push(self.root());
+        }
+
+        Some(Multiproof {
+            leaves: indices.iter().map(|i| self.nodes[*i]).collect(),
+            proof,
+            proof_flags,
+        })
+    }
+
+    fn index_of(&self, leaf: B256) -> Option<usize> {
+        let first_leaf = self.nodes.len() / 2;
+        (first_leaf..self.nodes.len()).find(|i| self.nodes[*i] == leaf)
+    }
+}
+
+fn sibling(index: usize) -> usize {
+    match index % 2 {
+        0 => index - 1,
+        _ => index + 1,
+    }
+}
+
+fn parent(index: usize) -> usize {
+    (index - 1) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_pair, leaf_hash, process_multiproof, verify, verify_multiproof};
+    use super::{MerkleTree, Multiproof};
+    use alloc::vec::Vec;
+    use alloy_primitives::{b256, Address, B256, U256};
+    use alloy_sol_types::SolValue;
+
+    #[test]
+    fn test_merkle() {
+        let leaves: Vec<_> = (1..=7_u8).map(B256::repeat_byte).collect();
+        let (a, b) = (leaves[0], leaves[1]);
+        assert_eq!(hash_pair(a, b), hash_pair(b, a));
+        assert_eq!(MerkleTree::new(alloc::vec![a, b]).root(), hash_pair(a, b));
+
+        let tree = MerkleTree::new(leaves.clone());
+        for leaf in &leaves {
+            let proof = tree.proof(*leaf).unwrap();
+            assert!(verify(&proof, tree.root(), *leaf));
+            assert!(!verify(&proof, tree.root(), B256::ZERO));
+        }
+        assert_eq!(tree.proof(B256::ZERO), None);
+
+        for subset in [&leaves[..], &leaves[2..5], &leaves[6..], &leaves[..0]] {
+            let multi = tree.multiproof(subset).unwrap();
+            let (proof, flags) = (&multi.proof, &multi.proof_flags);
+            assert!(verify_multiproof(proof, flags, tree.root(), &multi.leaves));
+            assert!(!verify_multiproof(proof, flags, B256::ZERO, &multi.leaves));
+        }
+
+        let single = MerkleTree::new(alloc::vec![a]);
+        assert!(verify(&[], single.root(), a));
+        assert_eq!(process_multiproof(&[], &[], &[a]), Some(a));
+        assert_eq!(process_multiproof(&[a], &[true], &[b]), None);
+        assert_eq!(process_multiproof(&[a, a], &[false], &[b]), None);
+    }
+
+    #[test]
+    fn test_openzeppelin_vectors() {
+        // accounts 0x1111..., 0x2222..., and so on, paired with amounts in wei
+        let amounts = [5_000, 2_500, 1_000, 500, 250].map(|milli| milli * 1_000_000_000_000_000);
+        let leaves: Vec<_> = (1..)
+            .zip(amounts)
+            .map(|(i, amount): (u8, u64)| {
+                let account = Address::repeat_byte(0x11 * i);
+                leaf_hash(&(account, U256::from(amount)).abi_encode())
+            })
+            .collect();
+
+        // the example in the README of @openzeppelin/merkle-tree
+        let tree = MerkleTree::new(leaves[..2].to_vec());
+        let root = b256!("d4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77");
+        let sibling = b256!("b92c48e9d7abe27fd8dfd6b5dfdbfb1c9a463f80c712b66f3a5180a090cccafc");
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.proof(leaves[0]).unwrap(), [sibling]);
+
+        // StandardMerkleTree.of(values, ["address", "uint256"]) for all five
+        let tree = MerkleTree::new(leaves.clone());
+        let root = b256!("4bb45650ad9fe3cd55d413b6c9625df220f060194a79f967a80c0d417fee733c");
+        assert_eq!(tree.root(), root);
+
+        let proof = [
+            b256!("eb02c421cfa48976e66dfb29120745909ea3a0f843456c263cf8f1253483e283"),
+            b256!("741bbdaf62cd6bc13ae46766b4a23a74153bc3609f7d010f449319101f3d85eb"),
+        ];
+        assert_eq!(tree.proof(leaves[2]).unwrap(), proof);
+        assert!(verify(&proof, root, leaves[2]));
+
+        // getMultiProof for values 0, 2, and 3
+        let multi = tree.multiproof(&[leaves[0], leaves[2], leaves[3]]).unwrap();
+        let expected = Multiproof {
+            leaves: alloc::vec![leaves[3], leaves[2], leaves[0]],
+            proof: alloc::vec![
+                sibling,
+                b256!("f74cbe97b2a2793e296a8d6404523d6bb7a7e10448aa8ccb19fa649f370fcd55"),
+            ],
+            proof_flags: alloc::vec![false, true, false, true],
+        };
+        assert_eq!(multi, expected);
+        assert!(verify_multiproof(
+            &multi.proof,
+            &multi.proof_flags,
+            root,
+            &multi.leaves
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2022-2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! VM-accelerated cryptography.
+//!
+//! Wrappers for the standard [`precompiles`], like [`ecrecover`] and [`sha256`], are also provided,
+//! along with the [`bn254`] curve operations used to verify SNARKs, [`p256`] signatures from
+//! passkeys, [`eip712`] typed data hashing, [`merkle`] proofs, and [`signature`] validation for
+//! both EOAs and contract wallets.
+//!
+//! See also [`block`](crate::block), [`contract`](crate::contract), [`evm`](crate::evm),
+//! [`msg`](crate::msg), and [`tx`](crate::tx).
+//!
+//! ```no_run
+//! use stylus_sdk::crypto;
+//! use stylus_sdk::alloy_primitives::address;
+//!
+//! let preimage = address!("361594F5429D23ECE0A88E4fBE529E1c49D524d8");
+//! let hash = crypto::keccak(&preimage);
+//! ```
+//!
+//! [`precompiles`]: https://www.evm.codes/precompiled
+
+use alloy_primitives::B256;
+
+#[cfg(target_arch = "wasm32")]
+use alloc::vec::Vec;
+
+pub use precompiles::{ecrecover, identity, modexp, ripemd160, sha256};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use precompiles::{mock_precompiles, PrecompileMock};
+
+pub mod bn254;
+pub mod eip712;
+pub mod merkle;
+pub mod p256;
+mod precompiles;
+pub mod signature;
+
+/// Efficiently computes the [`keccak256`] hash of the given preimage.
+///
+/// On `wasm32` this uses the VM's native implementation, falling back to pure Rust elsewhere, such
+/// as in tests.
+///
+/// [`keccak256`]: https://en.wikipedia.org/wiki/SHA-3
+pub fn keccak<T: AsRef<[u8]>>(bytes: T) -> B256 {
+    let bytes = bytes.as_ref();
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut output = B256::ZERO;
+        unsafe {
+            crate::hostio::native_keccak256(bytes.as_ptr(), bytes.len(), output.as_mut_ptr())
+        };
+        output
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut hasher = alloy_primitives::Keccak256::new();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+}
+
+/// Computes the [`keccak256`] hash of a preimage given in parts, as if they were concatenated.
+///
+/// Since the VM's native implementation requires a contiguous preimage, on `wasm32` the parts are
+/// gathered into a single buffer, which [`Keccak::with_capacity`] can allocate up front. Elsewhere,
+/// the parts are streamed through a pure Rust implementation.
+///
+/// ```
+/// use stylus_sdk::crypto::{self, Keccak};
+///
+/// let mut hasher = Keccak::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+/// assert_eq!(hasher.finalize(), crypto::keccak(b"hello world"));
+/// ```
+///
+/// [`keccak256`]: https://en.wikipedia.org/wiki/SHA-3
+#[derive(Clone, Default)]
+pub struct Keccak {
+    #[cfg(target_arch = "wasm32")]
+    buffer: Vec<u8>,
+    #[cfg(not(target_arch = "wasm32"))]
+    hasher: alloy_primitives::Keccak256,
+}
+
+impl Keccak {
+    /// Creates a new hasher.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new hasher expecting a preimage of about the given length.
+    #[allow(unused_variables)]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            #[cfg(target_arch = "wasm32")]
+            buffer: Vec::with_capacity(capacity),
+            #[cfg(not(target_arch = "wasm32"))]
+            hasher: Default::default(),
+        }
+    }
+
+    /// Appends to the preimage.
+    pub fn update<T: AsRef<[u8]>>(&mut self, bytes: T) {
+        #[cfg(target_arch = "wasm32")]
+        self.buffer.extend_from_slice(bytes.as_ref());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.hasher.update(bytes)
+    }
+
+    /// Computes the hash of the preimage.
+    pub fn finalize(self) -> B256 {
+        #[cfg(target_arch = "wasm32")]
+        {
+            keccak(self.buffer)
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.hasher.finalize()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keccak, Keccak};
+    use alloy_primitives::b256;
+
+    #[test]
+    fn test_keccak() {
+        let empty = b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+        assert_eq!(keccak([]), empty);
+        assert_eq!(Keccak::new().finalize(), empty);
+
+        let mut hasher = Keccak::with_capacity(64);
+        hasher.update([1; 40]);
+        hasher.update([2; 24]);
+        let mut preimage = [1; 64];
+        preimage[40..].fill(2);
+        assert_eq!(hasher.finalize(), keccak(preimage));
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Verification of signatures over the [`P-256`] curve, also known as `secp256r1`, as used by
+//! passkeys and [`WebAuthn`].
+//!
+//! Signatures are verified with the [`RIP-7212`] precompile, which Arbitrum chains provide.
+//! For chains without it, the `p256-fallback` feature verifies signatures in pure Rust whenever the
+//! precompile doesn't, at a much greater cost.
+//!
+//! ```no_run
+//! use stylus_sdk::crypto::p256::{self, WebAuthnAssertion};
+//! use stylus_sdk::alloy_primitives::B256;
+//!
+//! fn verify_passkey(
+//!     challenge: B256, auth_data: &[u8], client_data: &[u8], sig: (B256, B256), key: (B256, B256),
+//! ) -> bool {
+//!     let Ok(assertion) = WebAuthnAssertion::parse(auth_data, client_data) else {
+//!         return false;
+//!     };
+//!     let Some(hash) = assertion.message_hash() else {
+//!         return false;
+//!     };
+//!     assertion.challenge() == challenge.as_slice()
+//!         && assertion.user_present()
+//!         && p256::verify(hash, sig.0, sig.1, key.0, key.1)
+//! }
+//! ```
+//!
+//! [`P-256`]: https://neuromancer.sk/std/nist/P-256
+//! [`WebAuthn`]: https://www.w3.org/TR/webauthn-2/
+//! [`RIP-7212`]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+
+use super::precompiles::static_call_precompile;
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+
+/// Verifies a signature `(r, s)` of a hash by the public key `(x, y)`.
+///
+/// Note that, as with the precompile, malleable signatures with a high `s` are accepted.
+/// With the `p256-fallback` feature, any signature the precompile rejects is checked again in pure
+/// Rust, since invalid signatures and a missing precompile are indistinguishable.
+pub fn verify(hash: B256, r: B256, s: B256, x: B256, y: B256) -> bool {
+    let mut input = [0; 160];
+    for (chunk, word) in input.chunks_mut(32).zip([hash, r, s, x, y]) {
+        chunk.copy_from_slice(word.as_slice());
+    }
+
+    // the precompile returns nothing for invalid signatures, as do chains without it
+    let output = static_call_precompile(0x100, &input).unwrap_or_default();
+    if output.len() == 32 && output[31] == 1 {
+        return true;
+    }
+
+    #[cfg(feature = "p256-fallback")]
+    return verify_fallback(hash, r, s, x, y);
+
+    #[cfg(not(feature = "p256-fallback"))]
+    false
+}
+
+/// Verifies a signature in pure Rust.
+#[cfg(feature = "p256-fallback")]
+fn verify_fallback(hash: B256, r: B256, s: B256, x: B256, y: B256) -> bool {
+    use p256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+    use p256::EncodedPoint;
+
+    let point = EncodedPoint::from_affine_coordinates(&x.0.into(), &y.0.into(), false);
+    let Ok(key) = VerifyingKey::from_encoded_point(&point) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_scalars(r.0, s.0) else {
+        return false;
+    };
+    key.verify_prehash(hash.as_slice(), &signature).is_ok()
+}
+
+/// A [`WebAuthn`] assertion, whose signature covers the authenticator data and a hash of the
+/// client data.
+///
+/// [`WebAuthn`]: https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WebAuthnAssertion<'a> {
+    authenticator_data: &'a [u8],
+    client_data_json: &'a [u8],
+    challenge: &'a [u8],
+}
+
+/// Represents the ways a [`WebAuthnAssertion`] may be malformed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebAuthnError {
+    /// The authenticator data is shorter than the required 37 bytes.
+    AuthenticatorDataTooShort,
+    /// The client data's type isn't `webauthn.get`.
+    WrongType,
+    /// The client data has no challenge, or it isn't valid [`base64url`].
+    ///
+    /// [`base64url`]: https://datatracker.ietf.org/doc/html/rfc4648#section-5
+    InvalidChallenge,
+}
+
+impl<'a> WebAuthnAssertion<'a> {
+    /// Parses an assertion from its `authenticatorData` and `clientDataJSON`.
+    pub fn parse(
+        authenticator_data: &'a [u8],
+        client_data_json: &'a [u8],
+    ) -> Result<Self, WebAuthnError> {
+        if authenticator_data.len() < 37 {
+            return Err(WebAuthnError::AuthenticatorDataTooShort);
+        }
+        if json_string(client_data_json, b"type") != Some(b"webauthn.get") {
+            return Err(WebAuthnError::WrongType);
+        }
+        let challenge =
+            json_string(client_data_json, b"challenge").ok_or(WebAuthnError::InvalidChallenge)?;
+        if !challenge.iter().all(|c| base64url_value(*c).is_some()) || challenge.len() % 4 == 1 {
+            return Err(WebAuthnError::InvalidChallenge);
+        }
+        Ok(Self {
+            authenticator_data,
+            client_data_json,
+            challenge,
+        })
+    }
+
+    /// The challenge the client signed, decoded from [`base64url`].
+    /// Callers should check that this matches what they expect, such as the hash of an operation.
+    ///
+    /// [`base64url`]: https://datatracker.ietf.org/doc/html/rfc4648#section-5
+    pub fn challenge(&self) -> Vec<u8> {
+        base64url_decode(self.challenge)
+    }
+
+    /// The authenticator's flags.
+    pub fn flags(&self) -> u8 {
+        self.authenticator_data[32]
+    }
+
+    /// Whether the authenticator saw the user, as with a tap.
+    pub fn user_present(&self) -> bool {
+        self.flags() & 0x01 != 0
+    }
+
+    /// Whether the authenticator verified the user, as with a fingerprint.
+    pub fn user_verified(&self) -> bool {
+        self.flags() & 0x04 != 0
+    }
+
+    /// The message the authenticator signed, which is the authenticator data followed by the
+    /// SHA-256 hash of the client data. Returns `None` if the `sha256` precompile fails.
+    pub fn message(&self) -> Option<Vec<u8>> {
+        let mut message = self.authenticator_data.to_vec();
+        message.extend(super::sha256(self.client_data_json)?);
+        Some(message)
+    }
+
+    /// The SHA-256 hash of the [`message`](Self::message), which is what [`verify`] expects.
+    /// Returns `None` if the `sha256` precompile fails.
+    pub fn message_hash(&self) -> Option<B256> {
+        super::sha256(self.message()?)
+    }
+}
+
+/// Finds the string value of a top-level key in a JSON object, such as WebAuthn client data.
+///
+/// Nested objects, arrays, and strings are skipped over, so nothing inside them can pass for a
+/// top-level key. Returns `None` if the JSON is malformed, if the key is missing or repeated, or
+/// if its value isn't a string free of escapes, which WebAuthn types and challenges never need.
+fn json_string<'a>(json: &'a [u8], key: &[u8]) -> Option<&'a [u8]> {
+    let mut pos = skip_whitespace(json, 0);
+    if json.get(pos) != Some(&b'{') {
+        return None;
+    }
+    pos = skip_whitespace(json, pos + 1);
+    if json.get(pos) == Some(&b'}') {
+        return None;
+    }
+
+    let mut found = None;
+    loop {
+        let name_end = string_end(json, pos)?;
+        let name = &json[pos + 1..name_end];
+        pos = skip_whitespace(json, name_end + 1);
+        if json.get(pos) != Some(&b':') {
+            return None;
+        }
+        let start = skip_whitespace(json, pos + 1);
+        let end = value_end(json, start)?;
+
+        if name == key {
+            let value = json[start..end].strip_prefix(b"\"")?.strip_suffix(b"\"")?;
+            if found.is_some() || value.contains(&b'\\') {
+                return None;
+            }
+            found = Some(value);
+        }
+
+        pos = skip_whitespace(json, end);
+        match json.get(pos)? {
+            b',' => pos = skip_whitespace(json, pos + 1),
+            b'}' => return found,
+            _ => return None,
+        }
+    }
+}
+
+/// Finds the closing quote of the JSON string starting at `start`.
+fn string_end(json: &[u8], start: usize) -> Option<usize> {
+    if json.get(start) != Some(&b'"') {
+        return None;
+    }
+    let mut pos = start + 1;
+    loop {
+        match json.get(pos)? {
+            b'"' => return Some(pos),
+            b'\\' => pos += 2,
+            _ => pos += 1,
+        }
+    }
+}
+
+/// Finds the end of the JSON value starting at `start`, skipping over anything nested within it.
+fn value_end(json: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0_usize;
+    let mut pos = start;
+    loop {
+        let c = *json.get(pos)?;
+        match c {
+            b'"' => pos = string_end(json, pos)?,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth > 0 => depth -= 1,
+            b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r' if depth == 0 => {
+                return (pos > start).then_some(pos);
+            }
+            _ => {}
+        }
+        pos += 1;
+        if depth == 0 && matches!(c, b'"' | b'}' | b']') {
+            return Some(pos);
+        }
+    }
+}
+
+fn skip_whitespace(json: &[u8], mut pos: usize) -> usize {
+    while matches!(json.get(pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+        pos += 1;
+    }
+    pos
+}
+
+fn base64url_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes unpadded [`base64url`], assuming each character is valid.
+///
+/// [`base64url`]: https://datatracker.ietf.org/doc/html/rfc4648#section-5
+fn base64url_decode(encoded: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+    for c in encoded {
+        buffer = (buffer << 6) | base64url_value(*c).unwrap_or_default() as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64url_decode, json_string, verify, WebAuthnAssertion, WebAuthnError};
+    use crate::crypto::{mock_precompiles, precompiles::tests::mock};
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_webauthn() {
+        let mut auth_data = [0; 37];
+        auth_data[32] = 0x05;
+        let client_data = br#"{"type":"webauthn.get","challenge":"3q2-7w","origin":"https://example.com","crossOrigin":false}"#;
+
+        let assertion = WebAuthnAssertion::parse(&auth_data, client_data).unwrap();
+        assert_eq!(assertion.challenge(), [0xde, 0xad, 0xbe, 0xef]);
+        assert!(assertion.user_present() && assertion.user_verified());
+
+        let create = br#"{"type":"webauthn.create","challenge":"3q2-7w"}"#;
+        let invalid = br#"{"type":"webauthn.get","challenge":"3q2+7w"}"#;
+        let parse = |auth: &[u8], client: &[u8]| WebAuthnAssertion::parse(auth, client).err();
+        assert_eq!(
+            parse(&auth_data[..36], client_data),
+            Some(WebAuthnError::AuthenticatorDataTooShort)
+        );
+        assert_eq!(parse(&auth_data, create), Some(WebAuthnError::WrongType));
+        assert_eq!(
+            parse(&auth_data, invalid),
+            Some(WebAuthnError::InvalidChallenge)
+        );
+
+        assert_eq!(base64url_decode(b"aGVsbG8"), b"hello");
+        assert_eq!(base64url_decode(b"_-8"), [0xff, 0xef]);
+    }
+
+    #[test]
+    fn test_json_string() {
+        let challenge = |json: &[u8]| json_string(json, b"challenge").map(<[u8]>::to_vec);
+        let spaced = b" {\n \"type\" : \"webauthn.get\" ,\n \"challenge\" : \"3q2-7w\"\n} ";
+        assert_eq!(challenge(spaced), Some(b"3q2-7w".to_vec()));
+
+        // keys nested in other fields aren't top-level ones
+        let nested =
+            br#"{"other":{"challenge":"AAAA"},"list":[{"challenge":"AAAA"}],"challenge":"3q2-7w"}"#;
+        let quoted = br#"{"origin":"\"challenge\":\"AAAA\"","challenge":"3q2-7w"}"#;
+        assert_eq!(challenge(nested), Some(b"3q2-7w".to_vec()));
+        assert_eq!(challenge(quoted), Some(b"3q2-7w".to_vec()));
+        assert_eq!(challenge(br#"{"other":{"challenge":"AAAA"}}"#), None);
+        assert_eq!(challenge(br#"{"origin":"\"challenge\":\"AAAA\""}"#), None);
+
+        // neither repeated keys, escaped values, nor other types are accepted
+        assert_eq!(
+            challenge(br#"{"challenge":"3q2-7w","challenge":"AAAA"}"#),
+            None
+        );
+        assert_eq!(challenge(br#"{"challenge":"3q2\u002d7w"}"#), None);
+        assert_eq!(challenge(br#"{"challenge":["3q2-7w"]}"#), None);
+        assert_eq!(challenge(br#"{"challenge":"3q2-7w""#), None);
+        assert_eq!(challenge(b"{}"), None);
+
+        let auth_data = [0; 37];
+        let client_data = br#"{"x":{"type":"webauthn.get"},"challenge":"3q2-7w"}"#;
+        assert_eq!(
+            WebAuthnAssertion::parse(&auth_data, client_data).err(),
+            Some(WebAuthnError::WrongType)
+        );
+        let client_data = br#"{"type":"webauthn.get","x":{"challenge":"3q2-7w"}}"#;
+        assert_eq!(
+            WebAuthnAssertion::parse(&auth_data, client_data).err(),
+            Some(WebAuthnError::InvalidChallenge)
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        mock_precompiles(mock);
+
+        // the mock precompile accepts this hash and rejects everything else
+        let zero = B256::ZERO;
+        assert!(verify(B256::repeat_byte(0xaa), zero, zero, zero, zero));
+        assert!(!verify(B256::repeat_byte(3), zero, zero, zero, zero));
+    }
+
+    #[cfg(feature = "p256-fallback")]
+    #[test]
+    fn test_verify_fallback() {
+        use super::verify_fallback;
+        use p256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+        let key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let point = key.verifying_key().to_encoded_point(false);
+        let x = B256::from_slice(point.x().unwrap());
+        let y = B256::from_slice(point.y().unwrap());
+
+        let hash = B256::repeat_byte(3);
+        let signature: Signature = key.sign_prehash(hash.as_slice()).unwrap();
+        let r = B256::from_slice(&signature.r().to_bytes());
+        let s = B256::from_slice(&signature.s().to_bytes());
+
+        assert!(verify_fallback(hash, r, s, x, y));
+        assert!(!verify_fallback(B256::ZERO, r, s, x, y));
+        assert!(!verify_fallback(hash, r, s, y, x));
+
+        // signatures the precompile rejects are checked again
+        mock_precompiles(mock);
+        assert!(verify(hash, r, s, x, y));
+        assert!(!verify(hash, r, s, y, x));
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use alloc::vec::Vec;
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+
+#[cfg(target_arch = "wasm32")]
+use crate::call::RawCall;
+
+#[cfg(not(target_arch = "wasm32"))]
+use core::{
+    mem, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Recovers the signer of a hash from its [`ECDSA`] signature via the `ecrecover` precompile.
+///
+/// Returns `None` if the signature is invalid. Note that `v` is either `27` or `28`, and that
+/// malleable signatures with a high `s` are accepted.
+///
+/// [`ECDSA`]: https://en.wikipedia.org/wiki/Elliptic_Curve_Digital_Signature_Algorithm
+pub fn ecrecover(hash: B256, v: u8, r: B256, s: B256) -> Option<Address> {
+    let output = static_call_precompile(0x01, &ecrecover_input(hash, v, r, s)).ok()?;
+    parse_address(&output)
+}
+
+/// Computes the [`SHA-256`] hash of the given preimage via the `sha256` precompile.
+///
+/// Returns `None` if the precompile fails or returns something other than a hash, as happens
+/// on chains without it.
+///
+/// [`SHA-256`]: https://en.wikipedia.org/wiki/SHA-2
+pub fn sha256<T: AsRef<[u8]>>(bytes: T) -> Option<B256> {
+    let output = static_call_precompile(0x02, bytes.as_ref()).ok()?;
+    B256::try_from(output.as_slice()).ok()
+}
+
+/// Computes the [`RIPEMD-160`] hash of the given preimage via the `ripemd160` precompile.
+///
+/// Returns `None` if the precompile fails or returns something other than a left-padded hash,
+/// as happens on chains without it.
+///
+/// [`RIPEMD-160`]: https://en.wikipedia.org/wiki/RIPEMD
+pub fn ripemd160<T: AsRef<[u8]>>(bytes: T) -> Option<FixedBytes<20>> {
+    let output = static_call_precompile(0x03, bytes.as_ref()).ok()?;
+    let word = B256::try_from(output.as_slice()).ok()?;
+    Some(FixedBytes::from_slice(&word[12..]))
+}
+
+/// Copies the given data via the `identity` precompile.
+///
+/// Returns `None` if the precompile fails or returns data of the wrong length.
+pub fn identity<T: AsRef<[u8]>>(bytes: T) -> Option<Vec<u8>> {
+    let bytes = bytes.as_ref();
+    let output = static_call_precompile(0x04, bytes).ok()?;
+    (output.len() == bytes.len()).then_some(output)
+}
+
+/// Computes `base ^ exponent % modulus` via the `modexp` precompile, where each is a big-endian
+/// integer of arbitrary length. The result has the same length as the modulus.
+///
+/// Returns `None` if the precompile fails, which happens when it runs out of gas, or if the result
+/// has the wrong length.
+pub fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Option<Vec<u8>> {
+    let output = static_call_precompile(0x05, &modexp_input(base, exponent, modulus)).ok()?;
+    (output.len() == modulus.len()).then_some(output)
+}
+
+/// Handles a static call to the precompile at the given address, returning its output or revert
+/// data. See [`mock_precompiles`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type PrecompileMock = fn(Address, &[u8]) -> Result<Vec<u8>, Vec<u8>>;
+
+/// The installed [`PrecompileMock`], if any.
+#[cfg(not(target_arch = "wasm32"))]
+static MOCK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Routes precompile calls through the given function outside of `wasm32`, such as in native
+/// tests, where there's no VM to make them. The mock applies to the whole process, so tests that
+/// run in parallel should install the same one.
+///
+/// Without a mock, precompile calls behave as they would on a chain without precompiles,
+/// succeeding with no output.
+///
+/// ```
+/// use stylus_sdk::{crypto, alloy_primitives::{Address, B256}};
+///
+/// fn mock(precompile: Address, input: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+///     match precompile == Address::with_last_byte(2) {
+///         true => Ok(vec![input.len() as u8; 32]),
+///         false => Err(vec![]),
+///     }
+/// }
+///
+/// crypto::mock_precompiles(mock);
+/// assert_eq!(crypto::sha256(b"hello"), Some(B256::repeat_byte(5)));
+/// assert_eq!(crypto::identity(b"hello"), None);
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mock_precompiles(mock: PrecompileMock) {
+    MOCK.store(mock as *mut (), Ordering::Release);
+}
+
+/// Static calls the precompile at the given address.
+pub(crate) fn static_call_precompile(precompile: u16, input: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    let precompile = Address::left_padding_from(&precompile.to_be_bytes());
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        // safety: precompiles can't reenter, so no storage caching concerns apply
+        #[allow(unused_unsafe)]
+        unsafe {
+            RawCall::new_static().call(precompile, input)
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mock = MOCK.load(Ordering::Acquire);
+        if mock.is_null() {
+            return Ok(Vec::new());
+        }
+        // safety: only PrecompileMocks are stored
+        let mock = unsafe { mem::transmute::<*mut (), PrecompileMock>(mock) };
+        mock(precompile, input)
+    }
+}
+
+fn ecrecover_input(hash: B256, v: u8, r: B256, s: B256) -> [u8; 128] {
+    let mut input = [0; 128];
+    input[..32].copy_from_slice(hash.as_slice());
+    input[63] = v;
+    input[64..96].copy_from_slice(r.as_slice());
+    input[96..].copy_from_slice(s.as_slice());
+    input
+}
+
+/// Parses the left-padded address `ecrecover` returns, which is empty on failure.
+fn parse_address(output: &[u8]) -> Option<Address> {
+    let word = output.get(..32)?;
+    Some(Address::from_slice(&word[12..]))
+}
+
+fn modexp_input(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(96 + base.len() + exponent.len() + modulus.len());
+    for len in [base.len(), exponent.len(), modulus.len()] {
+        input.extend(U256::from(len).to_be_bytes::<32>());
+    }
+    input.extend(base);
+    input.extend(exponent);
+    input.extend(modulus);
+    input
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::{ecrecover_input, mock_precompiles, modexp_input, parse_address};
+    use crate::crypto::{ecrecover, identity, modexp, ripemd160, sha256};
+    use alloy_primitives::{address, b256, fixed_bytes, hex, Address, B256};
+
+    #[test]
+    fn test_precompile_encoding() {
+        let hash = B256::repeat_byte(1);
+        let input = ecrecover_input(hash, 27, B256::repeat_byte(2), B256::repeat_byte(3));
+        assert_eq!(input[..32], [1; 32]);
+        assert_eq!(input[32..63], [0; 31]);
+        assert_eq!(input[63], 27);
+        assert_eq!(input[64..96], [2; 32]);
+        assert_eq!(input[96..], [3; 32]);
+
+        let mut output = [0; 32];
+        output[12..].copy_from_slice(&[9; 20]);
+        assert_eq!(parse_address(&output), Some(Address::repeat_byte(9)));
+        assert_eq!(parse_address(&[]), None);
+
+        let input = modexp_input(&[3], &[2, 0], &[5, 0, 0]);
+        assert_eq!(input.len(), 96 + 6);
+        assert_eq!((input[31], input[63], input[95]), (1, 2, 3));
+        assert_eq!(input[96..], [3, 2, 0, 5, 0, 0]);
+    }
+
+    /// Stands in for the precompiles with canned responses.
+    ///
+    /// Since the hook is process-wide, every test that installs one uses this.
+    pub(in crate::crypto) fn mock(precompile: Address, input: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        let word = |data: &[u8]| {
+            let mut word = vec![0; 32 - data.len()];
+            word.extend(data);
+            word
+        };
+        match (precompile.0[19], input) {
+            (_, b"revert") => Err(b"failed".to_vec()),
+            (_, b"no code") => Ok(vec![]),
+            (0x01, input) if input[63] == 27 => Ok(word(&[input[0]; 20])),
+            (0x01, _) => Ok(vec![]),
+            (0x02, b"abc") => Ok(hex!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            )
+            .to_vec()),
+            (0x02, _) => Ok(vec![1; 20]),
+            (0x03, b"abc") => Ok(word(&hex!("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"))),
+            (0x03, _) => Ok(vec![1; 20]),
+            (0x04, input) => Ok(input.to_vec()),
+            (0x05, input) if input == modexp_input(&[3], &[2], &[0, 5]) => Ok(vec![0, 4]),
+            (0x05, _) => Ok(vec![4]),
+            (0x00, input) if input.starts_with(&[0xaa; 32]) => Ok(word(&[1])),
+            _ => Err(vec![]),
+        }
+    }
+
+    #[test]
+    fn test_precompile_wrappers() {
+        mock_precompiles(mock);
+
+        let hash = b256!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(sha256(b"abc"), Some(hash));
+        assert_eq!(sha256(b"abcd"), None);
+        assert_eq!(sha256(b"no code"), None);
+        assert_eq!(sha256(b"revert"), None);
+
+        let hash = fixed_bytes!("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+        assert_eq!(ripemd160(b"abc"), Some(hash));
+        assert_eq!(ripemd160(b"abcd"), None);
+        assert_eq!(ripemd160(b"no code"), None);
+        assert_eq!(ripemd160(b"revert"), None);
+
+        let signer = address!("0909090909090909090909090909090909090909");
+        let (r, s) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        assert_eq!(ecrecover(B256::repeat_byte(9), 27, r, s), Some(signer));
+        assert_eq!(ecrecover(B256::repeat_byte(9), 28, r, s), None);
+
+        assert_eq!(identity(b"hello"), Some(b"hello".to_vec()));
+        assert_eq!(identity(b"no code"), None);
+        assert_eq!(identity(b"revert"), None);
+
+        assert_eq!(modexp(&[3], &[2], &[0, 5]), Some(vec![0, 4]));
+        assert_eq!(modexp(&[3], &[2], &[1, 5]), None);
+    }
+}
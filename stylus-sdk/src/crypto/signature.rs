@@ -0,0 +1,168 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Validation of signatures from both [`EOAs`] and contract wallets, much like OpenZeppelin's
+//! [`SignatureChecker`].
+//!
+//! Accounts with code are asked via [`ERC-1271`] whether they consider a signature valid, while
+//! signatures from EOAs are recovered with `ecrecover`.
+//!
+//! ```no_run
+//! use stylus_sdk::crypto::signature;
+//! use stylus_sdk::alloy_primitives::Address;
+//!
+//! fn signed_hello(signer: Address, sig: &[u8]) -> bool {
+//!     let hash = signature::to_eth_signed_message_hash(b"hello");
+//!     signature::is_valid_signature_now(signer, hash, sig)
+//! }
+//! ```
+//!
+//! [`EOAs`]: https://ethereum.org/en/developers/docs/accounts/#types-of-account
+//! [`SignatureChecker`]: https://docs.openzeppelin.com/contracts/5.x/api/utils#SignatureChecker
+//! [`ERC-1271`]: https://eips.ethereum.org/EIPS/eip-1271
+
+use super::{ecrecover, Keccak};
+use crate::{abi::Bytes, call::RawCall, types::AddressVM};
+use alloc::vec::Vec;
+use alloy_primitives::{b256, Address, FixedBytes, B256};
+use alloy_sol_types::SolValue;
+
+/// Half the order of the `secp256k1` curve, above which `s` values are considered malleable.
+const HALF_ORDER: B256 = b256!("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+
+/// The value [`ERC-1271`] wallets return for valid signatures, which is the selector of
+/// `isValidSignature(bytes32,bytes)`.
+///
+/// [`ERC-1271`]: https://eips.ethereum.org/EIPS/eip-1271
+pub const ERC1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Checks whether `signer` signed `hash`, either as an EOA or as an [`ERC-1271`] wallet.
+///
+/// Since wallets may change their signing rules, the result is only valid for the current call.
+///
+/// [`ERC-1271`]: https://eips.ethereum.org/EIPS/eip-1271
+pub fn is_valid_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    if signer.has_code() {
+        return is_valid_erc1271_signature_now(signer, hash, signature);
+    }
+    recover(hash, signature).is_some_and(|recovered| recovered == signer)
+}
+
+/// Asks the [`ERC-1271`] wallet `signer` whether it considers `signature` valid for `hash`.
+///
+/// [`ERC-1271`]: https://eips.ethereum.org/EIPS/eip-1271
+pub fn is_valid_erc1271_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    let calldata = erc1271_calldata(hash, signature);
+
+    let call = RawCall::new_static();
+    #[cfg(feature = "reentrant")]
+    let call = call.flush_storage_cache(); // the wallet may read back our state
+
+    // safety: static calls can't modify storage
+    #[allow(unused_unsafe)]
+    let returned = unsafe { call.call(signer, &calldata) };
+    returned.is_ok_and(|returned| is_magic_value(&returned))
+}
+
+/// Recovers the signer of a 65-byte `r ‖ s ‖ v` signature.
+///
+/// Unlike [`ecrecover`], malleable signatures are rejected: `s` must be in the lower half of the
+/// curve's order, and `v` must be `27` or `28`.
+pub fn recover(hash: B256, signature: &[u8]) -> Option<Address> {
+    let (v, r, s) = split_signature(signature)?;
+    ecrecover(hash, v, r, s).filter(|signer| !signer.is_zero())
+}
+
+/// Computes the [`EIP-191`] hash of a message, as signed by `personal_sign` and `eth_sign`.
+///
+/// This is `keccak256("\x19Ethereum Signed Message:\n" ‖ len(message) ‖ message)`. Passing a
+/// 32-byte hash gives the same result as Solidity's `MessageHashUtils.toEthSignedMessageHash`.
+///
+/// [`EIP-191`]: https://eips.ethereum.org/EIPS/eip-191
+pub fn to_eth_signed_message_hash<T: AsRef<[u8]>>(message: T) -> B256 {
+    let message = message.as_ref();
+    let prefix = alloc::format!("\x19Ethereum Signed Message:\n{}", message.len());
+
+    let mut hasher = Keccak::with_capacity(prefix.len() + message.len());
+    hasher.update(prefix);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Splits a signature into its `v`, `r`, and `s`, rejecting those that are malleable.
+fn split_signature(signature: &[u8]) -> Option<(u8, B256, B256)> {
+    let signature: &[u8; 65] = signature.try_into().ok()?;
+    let r = B256::from_slice(&signature[..32]);
+    let s = B256::from_slice(&signature[32..64]);
+    let v = signature[64];
+
+    if s > HALF_ORDER || !matches!(v, 27 | 28) {
+        return None;
+    }
+    Some((v, r, s))
+}
+
+fn erc1271_calldata(hash: B256, signature: &[u8]) -> Vec<u8> {
+    let mut calldata =
+        crate::function_selector!("isValidSignature", FixedBytes<32>, Bytes).to_vec();
+    calldata.extend((hash, Bytes(signature.to_vec())).abi_encode_params());
+    calldata
+}
+
+/// Checks that the first word returned is exactly the magic value, padded on the right.
+fn is_magic_value(returned: &[u8]) -> bool {
+    let Some(word) = returned.get(..32) else {
+        return false;
+    };
+    word[..4] == ERC1271_MAGIC_VALUE && word[4..].iter().all(|b| *b == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{erc1271_calldata, is_magic_value, split_signature, to_eth_signed_message_hash};
+    use super::{ERC1271_MAGIC_VALUE, HALF_ORDER};
+    use alloy_primitives::{b256, hex, B256, U256};
+
+    #[test]
+    fn test_signature() {
+        let hash = b256!("d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68");
+        assert_eq!(to_eth_signed_message_hash("hello world"), hash);
+        assert_eq!(
+            to_eth_signed_message_hash(B256::ZERO),
+            to_eth_signed_message_hash([0; 32])
+        );
+
+        let calldata = erc1271_calldata(B256::ZERO, &[1, 2, 3]);
+        assert_eq!(calldata[..4], ERC1271_MAGIC_VALUE);
+        assert_eq!(calldata.len(), 4 + 4 * 32);
+
+        let mut returned = [0; 32];
+        returned[..4].copy_from_slice(&ERC1271_MAGIC_VALUE);
+        assert!(is_magic_value(&returned));
+        assert!(!is_magic_value(&returned[..4]));
+        returned[31] = 1;
+        assert!(!is_magic_value(&returned));
+
+        let half_order = U256::from_be_bytes(HALF_ORDER.0) * U256::from(2) + U256::from(1);
+        assert_eq!(
+            half_order,
+            U256::from_be_bytes(hex!(
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+            ))
+        );
+
+        let mut signature = [0; 65];
+        signature[..32].copy_from_slice(&[1; 32]);
+        signature[32..64].copy_from_slice(HALF_ORDER.as_slice());
+        signature[64] = 28;
+        let (v, r, s) = split_signature(&signature).unwrap();
+        assert_eq!((v, r, s), (28, B256::repeat_byte(1), HALF_ORDER));
+
+        signature[63] += 1;
+        assert_eq!(split_signature(&signature), None);
+        signature[63] -= 1;
+        signature[64] = 1;
+        assert_eq!(split_signature(&signature), None);
+        assert_eq!(split_signature(&signature[..64]), None);
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use crate::{contract, crypto::Keccak};
+use alloy_primitives::{Address, B256};
+
+/// Computes the address at which `deployer` creates a contract via [`CREATE`] when its nonce is
+/// `nonce`, which is the last 20 bytes of the hash of the [`RLP`] encoding of `[deployer, nonce]`.
+///
+/// [`CREATE`]: https://www.evm.codes/#f0
+/// [`RLP`]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp
+pub fn create_address(deployer: Address, nonce: u64) -> Address {
+    // nonces below 0x80 encode as themselves, except for 0, which is the empty string
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+    let nonce_header = match nonce {
+        1..=0x7f => None,
+        _ => Some(0x80 + nonce_bytes.len() as u8),
+    };
+
+    // the list is always shorter than 56 bytes, so its length fits in the header
+    let len = 21 + nonce_header.is_some() as u8 + nonce_bytes.len() as u8;
+    let mut hasher = Keccak::with_capacity(len as usize + 1);
+    hasher.update([0xc0 + len, 0x80 + 20]);
+    hasher.update(deployer);
+    if let Some(header) = nonce_header {
+        hasher.update([header]);
+    }
+    hasher.update(nonce_bytes);
+    Address::from_word(hasher.finalize())
+}
+
+/// Computes the address at which `deployer` creates a contract via [`CREATE2`] given the `salt`
+/// and the hash of the init code.
+///
+/// [`CREATE2`]: https://www.evm.codes/#f5
+pub fn create2_address(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    let mut hasher = Keccak::with_capacity(85);
+    hasher.update([0xff]);
+    hasher.update(deployer);
+    hasher.update(salt);
+    hasher.update(init_code_hash);
+    Address::from_word(hasher.finalize())
+}
+
+/// Computes the address at which the current program creates a contract via [`CREATE`] when
+/// its nonce is `nonce`. See [`create_address`] for more information.
+///
+/// [`CREATE`]: https://www.evm.codes/#f0
+pub fn contract_create_address(nonce: u64) -> Address {
+    create_address(contract::address(), nonce)
+}
+
+/// Computes the address at which the current program creates a contract via [`CREATE2`] given
+/// the `salt` and the hash of the init code. See [`create2_address`] for more information.
+///
+/// [`CREATE2`]: https://www.evm.codes/#f5
+pub fn contract_create2_address(salt: B256, init_code_hash: B256) -> Address {
+    create2_address(contract::address(), salt, init_code_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create2_address, create_address};
+    use crate::crypto;
+    use alloy_primitives::{address, b256, Address, B256};
+
+    #[test]
+    fn test_create_address() {
+        let deployer = address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        let expected = [
+            address!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            address!("f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            address!("fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        ];
+        for (nonce, expected) in expected.into_iter().enumerate() {
+            assert_eq!(create_address(deployer, nonce as u64), expected);
+        }
+
+        // nonces on either side of each change in length
+        let expected = [
+            (0x7f, address!("06d9a77f5e4b311bae8d559db9cdb4df94104aa0")),
+            (0x80, address!("08e190dcb7b73f5fcdabb43e102215c83659a76d")),
+            (0x100, address!("3837c1ae70354f670550c746580199ac6a73cb0a")),
+            (
+                u64::MAX,
+                address!("9bc924993b60399df164c3763a964301d3db95ca"),
+            ),
+        ];
+        for (nonce, expected) in expected {
+            assert_eq!(create_address(deployer, nonce), expected);
+        }
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // from EIP-1014
+        let hash = crypto::keccak([0x00]);
+        let deployer = Address::ZERO;
+        let expected = address!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38");
+        assert_eq!(create2_address(deployer, B256::ZERO, hash), expected);
+
+        let deployer = address!("00000000000000000000000000000000deadbeef");
+        let salt = b256!("00000000000000000000000000000000000000000000000000000000cafebabe");
+        let hash = crypto::keccak([0xde, 0xad, 0xbe, 0xef]);
+        let expected = address!("60f3f640a8508fC6a86d45DF051962668E1e8AC7");
+        assert_eq!(create2_address(deployer, salt, hash), expected);
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::RawDeploy;
+use crate::crypto;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, U256};
+
+/// Init code deploying an [`EIP-1167`] minimal proxy, which delegates every call to its
+/// implementation. The runtime code is 45 bytes, preceded by 10 bytes that return it.
+///
+/// [`EIP-1167`]: https://eips.ethereum.org/EIPS/eip-1167
+pub fn clone_init_code(implementation: Address) -> [u8; 55] {
+    const PREFIX: [u8; 20] = [
+        0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, // return the runtime code
+        0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73, // copy calldata, push20
+    ];
+    const SUFFIX: [u8; 15] = [
+        0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+    ];
+
+    let mut code = [0; 55];
+    code[..20].copy_from_slice(&PREFIX);
+    code[20..40].copy_from_slice(implementation.as_slice());
+    code[40..].copy_from_slice(&SUFFIX);
+    code
+}
+
+/// Deploys an [`EIP-1167`] minimal proxy of `implementation` via [`CREATE`].
+/// Returns the address of the clone, or the error data in case of failure.
+///
+/// [`EIP-1167`]: https://eips.ethereum.org/EIPS/eip-1167
+/// [`CREATE`]: https://www.evm.codes/#f0
+pub fn clone(implementation: Address) -> Result<Address, Vec<u8>> {
+    let code = clone_init_code(implementation);
+
+    // safety: the init code makes no calls, so it can't reenter
+    unsafe { RawDeploy::new().deploy(&code, U256::ZERO) }
+}
+
+/// Deploys an [`EIP-1167`] minimal proxy of `implementation` via [`CREATE2`], whose address
+/// [`predict_deterministic_address`] computes ahead of time.
+/// Returns the address of the clone, or the error data in case of failure.
+///
+/// [`EIP-1167`]: https://eips.ethereum.org/EIPS/eip-1167
+/// [`CREATE2`]: https://www.evm.codes/#f5
+pub fn clone_deterministic(implementation: Address, salt: B256) -> Result<Address, Vec<u8>> {
+    let code = clone_init_code(implementation);
+
+    // safety: the init code makes no calls, so it can't reenter
+    unsafe { RawDeploy::new().salt(salt).deploy(&code, U256::ZERO) }
+}
+
+/// Computes the address at which `deployer` would create a clone of `implementation` via
+/// [`clone_deterministic`].
+pub fn predict_deterministic_address(
+    implementation: Address,
+    salt: B256,
+    deployer: Address,
+) -> Address {
+    let code_hash = crypto::keccak(clone_init_code(implementation));
+    super::create2_address(deployer, salt, code_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clone_init_code, predict_deterministic_address};
+    use alloy_primitives::{address, b256, hex};
+
+    #[test]
+    fn test_clone() {
+        let implementation = address!("bebebebebebebebebebebebebebebebebebebebe");
+        let code = clone_init_code(implementation);
+        let expected = hex!(
+            "3d602d80600a3d3981f3363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe"
+            "5af43d82803e903d91602b57fd5bf3"
+        );
+        assert_eq!(code, expected);
+
+        let salt = b256!("00000000000000000000000000000000000000000000000000000000000000ff");
+        let deployer = address!("0000000000000000000000000000000000000001");
+        let predicted = predict_deterministic_address(implementation, salt, deployer);
+        assert_eq!(
+            predicted,
+            address!("35f20A44c4Baf54493d458CFC2fBcF5Eb5EfE1F0")
+        );
+    }
+}
@@ -3,9 +3,22 @@
 
 //! Deploy other contracts.
 //!
-//! Currently this module only supports low-level contract creation via [`RawDeploy`],
-//! but work is being done to introduce high-level deployment patterns.
+//! Low-level contract creation is done via [`RawDeploy`], on top of which this module provides
+//! higher-level deployment patterns, like the [`EIP-1167`] minimal proxies made by [`clone`].
+//! Addresses may be computed ahead of time via [`create_address`] and [`create2_address`].
+//!
+//! To deploy a Stylus program from its WASM, see [`StylusDeployer`].
+//!
+//! [`EIP-1167`]: https://eips.ethereum.org/EIPS/eip-1167
 
+pub use address::{
+    contract_create2_address, contract_create_address, create2_address, create_address,
+};
+pub use clone::{clone, clone_deterministic, clone_init_code, predict_deterministic_address};
 pub use raw::RawDeploy;
+pub use stylus::{DeployError, StylusDeployer};
 
+mod address;
+mod clone;
 mod raw;
+mod stylus;
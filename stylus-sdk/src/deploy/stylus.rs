@@ -0,0 +1,255 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::RawDeploy;
+use crate::call::{self, MutatingCallContext, RawCall};
+use alloc::vec::Vec;
+use alloy_primitives::{address, Address, B256, U256};
+
+/// The header marking code as that of a Stylus program, followed by the dictionary byte of its
+/// [`Brotli`]-compressed WASM, which here is the empty dictionary.
+///
+/// [`Brotli`]: https://www.rfc-editor.org/rfc/rfc7932
+const STYLUS_PREFIX: [u8; 4] = [0xef, 0xf0, 0x00, 0x00];
+
+/// The magic bytes beginning every WASM binary.
+const WASM_MAGIC: [u8; 4] = *b"\0asm";
+
+/// The `ArbWasm` precompile, which activates Stylus programs.
+const ARB_WASM: Address = address!("0000000000000000000000000000000000000071");
+
+/// Represents the ways deploying a Stylus program may fail.
+#[derive(Debug, PartialEq)]
+pub enum DeployError {
+    /// Revert data returned when creating the program.
+    Create(Vec<u8>),
+    /// Revert data returned by `ArbWasm` when activating the program.
+    Activate(Vec<u8>),
+    /// The constructor failed.
+    Construct(call::Error),
+}
+
+impl From<DeployError> for Vec<u8> {
+    fn from(err: DeployError) -> Vec<u8> {
+        match err {
+            DeployError::Create(data) | DeployError::Activate(data) => data,
+            DeployError::Construct(err) => err.into(),
+        }
+    }
+}
+
+/// Deploys Stylus programs from their WASM, generating the EVM init code returning it.
+///
+/// The WASM may be uncompressed, [`Brotli`]-compressed as `cargo stylus` does, or already
+/// prefixed with the `0xEFF000` header. New programs must be activated before they can be called,
+/// unless a program with the same code already was.
+///
+/// ```ignore
+/// use stylus_sdk::{call::Call, deploy::StylusDeployer};
+///
+/// sol_interface! {
+///     interface IToken {
+///         function init(address owner) external;
+///     }
+/// }
+///
+/// let token: IToken = StylusDeployer::new(TOKEN_WASM)
+///     .activate(activation_fee)
+///     .constructor(calldata)
+///     .deploy(Call::new_in(self))?;
+/// ```
+///
+/// [`Brotli`]: https://www.rfc-editor.org/rfc/rfc7932
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct StylusDeployer {
+    init_code: Vec<u8>,
+    salt: Option<B256>,
+    activation_fee: Option<U256>,
+    constructor: Option<Vec<u8>>,
+}
+
+impl StylusDeployer {
+    /// Begin configuring the deploy of the given WASM, which may be compressed or not.
+    ///
+    /// Raw WASM is wrapped in [`Brotli`] stored blocks, which don't compress it at all. Since
+    /// Stylus limits programs to 24 KB once compressed, such programs will likely be too large to
+    /// activate. Prefer the compressed output of `cargo stylus`, which is deployed as is.
+    ///
+    /// [`Brotli`]: https://www.rfc-editor.org/rfc/rfc7932
+    pub fn new(wasm: &[u8]) -> Self {
+        let mut code = Vec::with_capacity(wasm.len() + 4);
+        if wasm.starts_with(&WASM_MAGIC) {
+            code.extend(STYLUS_PREFIX);
+            brotli_store(wasm, &mut code);
+        } else if wasm.starts_with(&STYLUS_PREFIX[..3]) {
+            code.extend(wasm);
+        } else {
+            code.extend(STYLUS_PREFIX);
+            code.extend(wasm);
+        }
+
+        Self {
+            init_code: init_code(&code),
+            salt: None,
+            activation_fee: None,
+            constructor: None,
+        }
+    }
+
+    /// Configure the deploy to use the salt provided.
+    /// This will use [`CREATE2`] under the hood to provide a deterministic address, which
+    /// [`create2_address`](super::create2_address) computes from the hash of the
+    /// [`init_code`](Self::init_code).
+    ///
+    /// [`CREATE2`]: https://www.evm.codes/#f5
+    pub fn salt(mut self, salt: B256) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    /// Activate the program after deploying it, paying up to `fee` from the current program's
+    /// balance. `ArbWasm` refunds whatever the activation doesn't cost.
+    pub fn activate(mut self, fee: U256) -> Self {
+        self.activation_fee = Some(fee);
+        self
+    }
+
+    /// Call the program with the given ABI-encoded calldata, selector included, right after it's
+    /// deployed and activated. The program must be activated for this to succeed.
+    pub fn constructor(mut self, calldata: Vec<u8>) -> Self {
+        self.constructor = Some(calldata);
+        self
+    }
+
+    /// The EVM init code that returns the program's code.
+    pub fn init_code(&self) -> &[u8] {
+        &self.init_code
+    }
+
+    /// Deploys the program, returning a handle to it, such as an [`Address`] or a type generated
+    /// by [`sol_interface!`].
+    ///
+    /// Any value in the `context` is sent with the constructor call if there is one, or else with
+    /// the deployment itself. The `context`'s gas limit only applies to the constructor call, and
+    /// is otherwise ignored: deploying and activating always use all the gas available, minus the
+    /// 1/64th the EVM retains.
+    ///
+    /// [`sol_interface!`]: crate::prelude::sol_interface
+    pub fn deploy<I: From<Address>>(
+        self,
+        context: impl MutatingCallContext,
+    ) -> Result<I, DeployError> {
+        let endowment = match self.constructor {
+            Some(_) => U256::ZERO,
+            None => context.value(),
+        };
+
+        // safety: the init code makes no calls, so it can't reenter
+        let deploy = RawDeploy::new().salt_option(self.salt);
+        let program = unsafe { deploy.deploy(&self.init_code, endowment) };
+        let program = program.map_err(DeployError::Create)?;
+
+        if let Some(fee) = self.activation_fee {
+            let mut calldata = Vec::with_capacity(36);
+            calldata.extend(crate::function_selector!("activateProgram", Address));
+            calldata.extend(program.into_word());
+
+            // safety: precompiles can't reenter
+            #[allow(unused_unsafe)]
+            let activated = unsafe { RawCall::new_with_value(fee).call(ARB_WASM, &calldata) };
+            activated.map_err(DeployError::Activate)?;
+        }
+
+        if let Some(calldata) = &self.constructor {
+            call::call(context, program, calldata).map_err(DeployError::Construct)?;
+        }
+        Ok(program.into())
+    }
+}
+
+/// Generates EVM init code that returns the given code, as `cargo stylus` does.
+fn init_code(code: &[u8]) -> Vec<u8> {
+    const PRELUDE_LEN: u8 = 42;
+    let len = U256::from(code.len()).to_be_bytes::<32>();
+
+    let mut init = Vec::with_capacity(code.len() + PRELUDE_LEN as usize + 1);
+    init.push(0x7f); // PUSH32 len
+    init.extend(len);
+    init.push(0x80); // DUP1
+    init.extend([0x60, PRELUDE_LEN + 1]); // PUSH1 offset of the code
+    init.extend([0x60, 0x00]); // PUSH1 0
+    init.push(0x39); // CODECOPY
+    init.extend([0x60, 0x00]); // PUSH1 0
+    init.push(0xf3); // RETURN
+    init.push(0x00); // version
+    init.extend(code);
+    init
+}
+
+/// Encodes data as a [`Brotli`] stream without compressing it, using uncompressed meta-blocks.
+///
+/// [`Brotli`]: https://www.rfc-editor.org/rfc/rfc7932
+fn brotli_store(data: &[u8], out: &mut Vec<u8>) {
+    const MAX_BLOCK: usize = 1 << 16;
+    out.reserve(data.len() + 3 * (data.len() / MAX_BLOCK + 1) + 1);
+
+    // a leading 0 bit selects a 64 KiB window
+    let mut header_bits = 1;
+    for block in data.chunks(MAX_BLOCK) {
+        // ISLAST = 0, MNIBBLES = 4, MLEN - 1, then ISUNCOMPRESSED = 1
+        let mut header = ((block.len() as u32 - 1) << 3) | (1 << 19);
+        header <<= header_bits;
+        out.extend(&header.to_le_bytes()[..3]);
+        out.extend(block);
+        header_bits = 0;
+    }
+
+    // ISLAST = 1 and ISLASTEMPTY = 1
+    out.push(0b11 << header_bits);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brotli_store, StylusDeployer, STYLUS_PREFIX};
+    use alloc::vec::Vec;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_brotli_store() {
+        let mut out = Vec::new();
+        brotli_store(&[], &mut out);
+        assert_eq!(out, [0x06]);
+
+        let mut out = Vec::new();
+        brotli_store(b"hi", &mut out);
+        assert_eq!(out, hex!("100010" "6869" "03"));
+
+        let data = [7; (1 << 16) + 1];
+        let mut out = Vec::new();
+        brotli_store(&data, &mut out);
+        assert_eq!(out.len(), data.len() + 3 + 3 + 1);
+        assert_eq!(out[..3], hex!("f0ff1f"));
+        assert_eq!(out[3 + (1 << 16)..][..4], hex!("000008" "07"));
+    }
+
+    #[test]
+    fn test_init_code() {
+        let compressed = StylusDeployer::new(&[1, 2, 3]);
+        let code = compressed.init_code();
+        assert_eq!(code[0], 0x7f);
+        assert_eq!(code[31..33], [0, 7]);
+        assert_eq!(code[33..43], hex!("8060 2b 6000 39 6000 f3 00"));
+        assert_eq!(code[43..47], STYLUS_PREFIX);
+        assert_eq!(code[47..], [1, 2, 3]);
+
+        let prefixed = StylusDeployer::new(&code[43..]);
+        assert_eq!(prefixed.init_code(), code);
+
+        let wasm = StylusDeployer::new(b"\0asm");
+        assert_eq!(
+            wasm.init_code()[43..],
+            hex!("eff00000" "300010" "0061736d" "03")
+        );
+    }
+}
@@ -314,7 +314,7 @@ vm_hooks! {
     ///
     /// [`keccak256`]: https://en.wikipedia.org/wiki/SHA-3
     /// [`SHA3`]: https://www.evm.codes/#20
-    #[allow(unused)]
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
     pub fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8);
 
     /// Reads the program calldata. The semantics are equivalent to that of the EVM's
@@ -385,6 +385,55 @@ vm_hooks! {
     pub fn tx_origin(origin: *mut u8)
 }
 
+cfg_if! {
+    if #[cfg(all(test, not(target_arch = "wasm32")))] {
+        pub use native_transient::*;
+    } else {
+        vm_hooks! {
+            module("vm_hooks", vm_hooks_transient);
+
+            /// Reads a 32-byte value from transient storage, which is cleared at the end of every
+            /// transaction. The value will be `0` when not previously set within the transaction.
+            /// The semantics are equivalent to that of the EVM's [`TLOAD`] opcode.
+            ///
+            /// [`TLOAD`]: https://www.evm.codes/#5c
+            pub fn transient_load_bytes32(key: *const u8, dest: *mut u8);
+
+            /// Writes a 32-byte value to transient storage, which is cleared at the end of every
+            /// transaction. Unlike with permanent storage, the value isn't cached, so there is
+            /// nothing to flush. The semantics are equivalent to that of the EVM's [`TSTORE`]
+            /// opcode.
+            ///
+            /// [`TSTORE`]: https://www.evm.codes/#5d
+            pub fn transient_store_bytes32(key: *const u8, value: *const u8)
+        }
+    }
+}
+
+/// Stands in for the VM's transient storage in native tests, with one store per test thread.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod native_transient {
+    use std::{cell::RefCell, collections::HashMap, ptr, thread_local};
+
+    thread_local! {
+        static TRANSIENT: RefCell<HashMap<[u8; 32], [u8; 32]>> = RefCell::default();
+    }
+
+    /// Reads a 32-byte value from the test thread's transient storage.
+    pub unsafe fn transient_load_bytes32(key: *const u8, dest: *mut u8) {
+        let key = ptr::read(key as *const [u8; 32]);
+        let value = TRANSIENT.with(|store| store.borrow().get(&key).copied());
+        ptr::write(dest as *mut [u8; 32], value.unwrap_or_default());
+    }
+
+    /// Writes a 32-byte value to the test thread's transient storage.
+    pub unsafe fn transient_store_bytes32(key: *const u8, value: *const u8) {
+        let key = ptr::read(key as *const [u8; 32]);
+        let value = ptr::read(value as *const [u8; 32]);
+        TRANSIENT.with(|store| store.borrow_mut().insert(key, value));
+    }
+}
+
 vm_hooks! {
     #[allow(dead_code)]
     module("console", console);
@@ -37,6 +37,9 @@ static ALLOC: mini_alloc::MiniAlloc = mini_alloc::MiniAlloc::INIT;
 
 extern crate alloc;
 
+// lets the proc macros refer to the SDK by name from within it
+extern crate self as stylus_sdk;
+
 pub use alloy_primitives;
 pub use alloy_sol_types;
 pub use hex;
@@ -58,6 +61,7 @@ pub mod evm;
 pub mod methods;
 pub mod msg;
 pub mod prelude;
+pub mod proxy;
 pub mod storage;
 pub mod tx;
 pub mod types;
@@ -34,6 +34,16 @@ impl Purity {
                 | (Pure, Pure)
         )
     }
+
+    /// Returns the Solidity keyword for this purity, or `nonpayable` for [`Purity::Write`].
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Purity::Pure => "pure",
+            Purity::View => "view",
+            Purity::Write => "nonpayable",
+            Purity::Payable => "payable",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,112 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{impl_unit_storage, OwnershipTransferred, ProxyError, Upgraded};
+use crate::{evm, msg, prelude::*, storage::StorageAddress, types::AddressVM, ArbResult};
+use alloc::vec::Vec;
+use alloy_primitives::Address;
+
+/// A proxy that delegates all unmatched calls to the implementation of its beacon.
+///
+/// Many proxies may share a beacon, upgrading together when it does. The beacon is kept in the
+/// [`BEACON_SLOT`](super::BEACON_SLOT), and should be set via
+/// [`upgrade_beacon_to_and_call`](super::upgrade_beacon_to_and_call).
+#[derive(Debug, Default)]
+pub struct BeaconProxy;
+
+impl_unit_storage!(BeaconProxy);
+
+#[public]
+impl BeaconProxy {
+    /// Delegates the call to the beacon's implementation, returning whatever it does.
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, input: &[u8]) -> ArbResult {
+        let beacon = super::beacon();
+        let Some(implementation) = super::beacon_implementation(beacon) else {
+            return Err(ProxyError::InvalidBeacon(beacon).into());
+        };
+
+        // safety: the proxy trusts its implementation, and holds no accessors across the call
+        unsafe { super::delegate(implementation, input) }
+    }
+}
+
+/// A beacon holding the implementation of its [`BeaconProxy`] contracts, which its owner upgrades.
+///
+/// Unlike proxies, the beacon's fields live in regular storage. Since Stylus programs don't have
+/// constructors, call [`init`](UpgradeableBeacon::init) from a method of your own.
+#[storage]
+pub struct UpgradeableBeacon {
+    implementation: StorageAddress,
+    owner: StorageAddress,
+}
+
+#[public]
+impl UpgradeableBeacon {
+    /// Gets the current implementation.
+    pub fn implementation(&self) -> Address {
+        self.implementation.get()
+    }
+
+    /// Gets the account allowed to upgrade the beacon.
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+
+    /// Upgrades the beacon to a new implementation, emitting an [`Upgraded`] event.
+    pub fn upgrade_to(&mut self, new_implementation: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        Ok(self.set_implementation(new_implementation)?)
+    }
+
+    /// Transfers ownership of the beacon, emitting an [`OwnershipTransferred`] event.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        if new_owner.is_zero() {
+            return Err(ProxyError::InvalidAdmin(new_owner).into());
+        }
+        self.set_owner(new_owner);
+        Ok(())
+    }
+}
+
+impl UpgradeableBeacon {
+    /// Sets the initial implementation and owner, which may only happen once.
+    pub fn init(&mut self, implementation: Address, owner: Address) -> Result<(), ProxyError> {
+        if !self.owner.get().is_zero() {
+            return Err(ProxyError::UnauthorizedAccount(msg::sender()));
+        }
+        if owner.is_zero() {
+            return Err(ProxyError::InvalidAdmin(owner));
+        }
+        self.set_implementation(implementation)?;
+        self.set_owner(owner);
+        Ok(())
+    }
+
+    fn only_owner(&self) -> Result<(), ProxyError> {
+        let sender = msg::sender();
+        if sender != self.owner.get() {
+            return Err(ProxyError::UnauthorizedAccount(sender));
+        }
+        Ok(())
+    }
+
+    fn set_implementation(&mut self, implementation: Address) -> Result<(), ProxyError> {
+        if !implementation.has_code() {
+            return Err(ProxyError::InvalidBeaconImplementation(implementation));
+        }
+        self.implementation.set(implementation);
+        evm::log(Upgraded { implementation });
+        Ok(())
+    }
+
+    fn set_owner(&mut self, new_owner: Address) {
+        evm::log(OwnershipTransferred {
+            previous_owner: self.owner.get(),
+            new_owner,
+        });
+        self.owner.set(new_owner);
+    }
+}
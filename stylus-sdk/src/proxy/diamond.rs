@@ -0,0 +1,769 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Modular contracts via [`EIP-2535`] diamonds.
+//!
+//! A diamond maps each selector to a facet, delegating calls to whichever facet handles them.
+//! This allows a single contract to exceed the size limit, and to be upgraded a function at a time.
+//!
+//! ```ignore
+//! use stylus_sdk::{prelude::*, proxy::diamond::{self, Diamond, FacetCut, FacetCutAction}};
+//!
+//! #[storage]
+//! #[entrypoint]
+//! pub struct Protocol {
+//!     #[borrow]
+//!     diamond: Diamond,
+//! }
+//!
+//! #[public]
+//! #[inherit(Diamond)]
+//! impl Protocol {
+//!     pub fn init(&mut self, vault: Address) -> Result<(), Vec<u8>> {
+//!         self.diamond.init(msg::sender())?;
+//!         let cut = FacetCut {
+//!             facet_address: vault,
+//!             action: FacetCutAction::Add,
+//!             function_selectors: diamond::selectors::<Vault>(),
+//!         };
+//!         Ok(self.diamond.cut(vec![cut], Address::ZERO, &[])?)
+//!     }
+//! }
+//! ```
+//!
+//! The [`Diamond`] lives in [namespaced storage][namespace] so that it won't collide with the
+//! storage of its facets, which share the diamond's. Facets should likewise namespace their own.
+//!
+//! [`EIP-2535`]: https://eips.ethereum.org/EIPS/eip-2535
+//! [namespace]: crate::storage::erc7201_slot
+
+use super::{OwnershipTransferred, ProxyError};
+use crate::{
+    abi::{Bytes, Selectors},
+    contract, evm, msg,
+    prelude::*,
+    storage::{StorageAddress, StorageFixedBytes, StorageMap, StorageU256, StorageU32, StorageVec},
+    ArbResult,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, FixedBytes, U256, U32};
+use alloy_sol_types::{sol, SolError};
+
+sol! {
+    /// What a [`FacetCut`] does with its selectors.
+    #[derive(Debug, PartialEq)]
+    enum FacetCutAction {
+        /// Adds selectors that the diamond doesn't yet have.
+        Add,
+        /// Moves existing selectors to a different facet.
+        Replace,
+        /// Removes existing selectors, for which the facet must be the zero address.
+        Remove,
+    }
+
+    /// A change to the selectors of a facet.
+    #[derive(Debug, PartialEq)]
+    struct FacetCut {
+        /// The facet handling the selectors.
+        address facet_address;
+        /// What to do with the selectors.
+        FacetCutAction action;
+        /// The selectors to change.
+        bytes4[] function_selectors;
+    }
+
+    /// Emitted when the diamond's facets change.
+    event DiamondCut(FacetCut[] diamond_cut, address init, bytes data);
+
+    /// A cut's facet can't handle its selectors.
+    #[allow(missing_docs)]
+    error DiamondInvalidFacet(address facet);
+
+    /// A cut has no selectors.
+    #[allow(missing_docs)]
+    error DiamondNoSelectors(address facet);
+
+    /// A cut's action isn't a [`FacetCutAction`].
+    #[allow(missing_docs)]
+    error DiamondInvalidAction(uint8 action);
+
+    /// A selector being added already exists.
+    #[allow(missing_docs)]
+    error DiamondSelectorExists(bytes4 selector);
+
+    /// A selector being replaced or removed doesn't exist.
+    #[allow(missing_docs)]
+    error DiamondSelectorNotFound(bytes4 selector);
+
+    /// A selector is being replaced with the facet already handling it.
+    #[allow(missing_docs)]
+    error DiamondSameFacet(bytes4 selector);
+
+    /// The initializer isn't a contract, or calldata was given without one.
+    #[allow(missing_docs)]
+    error DiamondInvalidInit(address init);
+
+    /// No facet handles the selector called.
+    #[allow(missing_docs)]
+    error DiamondFunctionNotFound(bytes4 selector);
+
+    /// A cut changes one of the diamond's own functions, which can't be changed.
+    #[allow(missing_docs)]
+    error DiamondImmutableFunction(bytes4 selector);
+}
+
+/// Represents the ways an operation on a diamond may fail.
+#[derive(Debug, PartialEq)]
+pub enum DiamondError {
+    /// A cut's facet can't handle its selectors.
+    InvalidFacet(Address),
+    /// A cut has no selectors.
+    NoSelectors(Address),
+    /// A cut's action isn't a [`FacetCutAction`].
+    InvalidAction(u8),
+    /// A selector being added already exists.
+    SelectorExists(FixedBytes<4>),
+    /// A selector being replaced or removed doesn't exist.
+    SelectorNotFound(FixedBytes<4>),
+    /// A selector is being replaced with the facet already handling it.
+    SameFacet(FixedBytes<4>),
+    /// The initializer isn't a contract, or calldata was given without one.
+    InvalidInit(Address),
+    /// No facet handles the selector called.
+    FunctionNotFound(FixedBytes<4>),
+    /// A cut changes one of the diamond's own functions, which can't be changed.
+    ImmutableFunction(FixedBytes<4>),
+    /// The diamond's owner is invalid, or the caller isn't the owner.
+    Proxy(ProxyError),
+    /// Revert data returned by the initializer.
+    Revert(Vec<u8>),
+}
+
+impl From<ProxyError> for DiamondError {
+    fn from(err: ProxyError) -> Self {
+        Self::Proxy(err)
+    }
+}
+
+impl From<DiamondError> for Vec<u8> {
+    fn from(err: DiamondError) -> Vec<u8> {
+        use DiamondError::*;
+        match err {
+            InvalidFacet(facet) => DiamondInvalidFacet { facet }.abi_encode(),
+            NoSelectors(facet) => DiamondNoSelectors { facet }.abi_encode(),
+            InvalidAction(action) => DiamondInvalidAction { action }.abi_encode(),
+            SelectorExists(selector) => DiamondSelectorExists { selector }.abi_encode(),
+            SelectorNotFound(selector) => DiamondSelectorNotFound { selector }.abi_encode(),
+            SameFacet(selector) => DiamondSameFacet { selector }.abi_encode(),
+            InvalidInit(init) => DiamondInvalidInit { init }.abi_encode(),
+            FunctionNotFound(selector) => DiamondFunctionNotFound { selector }.abi_encode(),
+            ImmutableFunction(selector) => DiamondImmutableFunction { selector }.abi_encode(),
+            Proxy(err) => err.into(),
+            Revert(data) => data,
+        }
+    }
+}
+
+/// Lists the selectors of a `#[public]` type, including those it inherits, for use in a
+/// [`FacetCut`].
+pub fn selectors<T: Selectors>() -> Vec<FixedBytes<4>> {
+    let selectors = T::selectors().into_iter();
+    selectors
+        .map(|selector| selector.to_be_bytes().into())
+        .collect()
+}
+
+/// An [`EIP-2535`] diamond, which delegates calls to the facets handling their selectors.
+///
+/// Use via `#[inherit]` to expose `diamondCut` along with the loupe functions and [`ERC-173`]
+/// ownership. Since Stylus programs don't have constructors, call [`init`](Diamond::init) from
+/// a method of your own.
+///
+/// The diamond's own functions are immutable, and the loupe lists them under the diamond's
+/// address. Methods of the inheriting type also take precedence over facets, but aren't listed.
+///
+/// [`EIP-2535`]: https://eips.ethereum.org/EIPS/eip-2535
+/// [`ERC-173`]: https://eips.ethereum.org/EIPS/eip-173
+#[storage(namespace = "eip2535.diamond")]
+pub struct Diamond {
+    /// The facet handling each selector.
+    selector_facets: StorageMap<FixedBytes<4>, SelectorInfo>,
+    /// The selectors each facet handles.
+    facet_selectors: StorageMap<Address, StorageVec<StorageFixedBytes<4>>>,
+    /// Every facet handling at least one selector.
+    facets: StorageVec<StorageAddress>,
+    /// One more than each facet's index in `facets`, or zero if absent.
+    facet_positions: StorageMap<Address, StorageU256>,
+    /// The account allowed to cut the diamond.
+    owner: StorageAddress,
+}
+
+/// Where a selector lives.
+#[storage]
+#[derive(Erase)]
+struct SelectorInfo {
+    facet: StorageAddress,
+    /// The index of the selector in its facet's `facet_selectors`.
+    position: StorageU32,
+}
+
+#[public]
+impl Diamond {
+    /// Adds, replaces, or removes selectors, then delegate calls `init` with `data` if nonzero.
+    ///
+    /// Each cut is a `(facetAddress, action, functionSelectors)` tuple.
+    pub fn diamond_cut(
+        &mut self,
+        diamond_cut: Vec<(Address, u8, Vec<FixedBytes<4>>)>,
+        init: Address,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        let mut cuts = Vec::with_capacity(diamond_cut.len());
+        for (facet_address, action, function_selectors) in diamond_cut {
+            let Ok(action) = FacetCutAction::try_from(action) else {
+                return Err(DiamondError::InvalidAction(action).into());
+            };
+            cuts.push(FacetCut {
+                facet_address,
+                action,
+                function_selectors,
+            });
+        }
+        Ok(self.cut(cuts, init, &data)?)
+    }
+
+    /// Gets every facet along with the selectors it handles, starting with the diamond itself.
+    pub fn facets(&self) -> Vec<(Address, Vec<FixedBytes<4>>)> {
+        let facets = self.facet_addresses().into_iter();
+        facets
+            .map(|facet| (facet, self.facet_function_selectors(facet)))
+            .collect()
+    }
+
+    /// Gets the selectors a facet handles, which for the diamond itself are its own functions.
+    pub fn facet_function_selectors(&self, facet: Address) -> Vec<FixedBytes<4>> {
+        let mut functions = match facet == contract::address() {
+            true => selectors::<Self>(),
+            false => Vec::new(),
+        };
+        let selectors = self.facet_selectors.get(facet);
+        functions.extend((0..selectors.len()).filter_map(|i| selectors.get(i)));
+        functions
+    }
+
+    /// Gets every facet handling at least one selector, starting with the diamond itself.
+    pub fn facet_addresses(&self) -> Vec<Address> {
+        let diamond = contract::address();
+        let facets = (0..self.facets.len()).filter_map(|i| self.facets.get(i));
+        let mut addresses = alloc::vec![diamond];
+        addresses.extend(facets.filter(|&facet| facet != diamond));
+        addresses
+    }
+
+    /// Gets the facet handling a selector, or the zero address if none does.
+    /// The diamond's own functions are handled by the diamond.
+    pub fn facet_address(&self, selector: FixedBytes<4>) -> Address {
+        if selectors::<Self>().contains(&selector) {
+            return contract::address();
+        }
+        self.selector_facets.get(selector).facet.get()
+    }
+
+    /// Gets the account allowed to cut the diamond.
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+
+    /// Transfers ownership of the diamond, emitting an [`OwnershipTransferred`] event.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        if new_owner.is_zero() {
+            return Err(ProxyError::InvalidAdmin(new_owner).into());
+        }
+        self.set_owner(new_owner);
+        Ok(())
+    }
+
+    /// Delegates the call to the facet handling its selector, returning whatever it does.
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, input: &[u8]) -> ArbResult {
+        let selector = FixedBytes::from_slice(input.get(..4).unwrap_or(&[0; 4][..]));
+        let facet = self.selector_facets.get(selector).facet.get();
+        if facet.is_zero() {
+            return Err(DiamondError::FunctionNotFound(selector).into());
+        }
+
+        // safety: the diamond trusts its facets, and holds no accessors across the call
+        unsafe { super::delegate(facet, input) }
+    }
+}
+
+impl Diamond {
+    /// Sets the initial owner, which may only happen once.
+    pub fn init(&mut self, owner: Address) -> Result<(), DiamondError> {
+        if !self.owner.get().is_zero() {
+            return Err(ProxyError::UnauthorizedAccount(msg::sender()).into());
+        }
+        if owner.is_zero() {
+            return Err(ProxyError::InvalidAdmin(owner).into());
+        }
+        self.set_owner(owner);
+        Ok(())
+    }
+
+    /// Applies cuts without checking the caller, emitting a [`DiamondCut`] event.
+    /// If `init` is nonzero, it's then delegate called with `data`.
+    ///
+    /// Cuts may not change the diamond's own functions.
+    pub fn cut(
+        &mut self,
+        cuts: Vec<FacetCut>,
+        init: Address,
+        data: &[u8],
+    ) -> Result<(), DiamondError> {
+        let immutable = selectors::<Self>();
+        for cut in &cuts {
+            let facet = cut.facet_address;
+            if cut.function_selectors.is_empty() {
+                return Err(DiamondError::NoSelectors(facet));
+            }
+            match cut.action {
+                FacetCutAction::Add | FacetCutAction::Replace if !facet.has_code() => {
+                    return Err(DiamondError::InvalidFacet(facet));
+                }
+                FacetCutAction::Remove if !facet.is_zero() => {
+                    return Err(DiamondError::InvalidFacet(facet));
+                }
+                _ => {}
+            }
+            for &selector in &cut.function_selectors {
+                if immutable.contains(&selector) {
+                    return Err(DiamondError::ImmutableFunction(selector));
+                }
+                cut_selector(self, cut.action, facet, selector)?;
+            }
+        }
+
+        evm::log(DiamondCut {
+            diamond_cut: cuts,
+            init,
+            data: data.to_vec().into(),
+        });
+
+        if init.is_zero() {
+            if !data.is_empty() {
+                return Err(DiamondError::InvalidInit(init));
+            }
+            return Ok(());
+        }
+        if !init.has_code() {
+            return Err(DiamondError::InvalidInit(init));
+        }
+
+        // safety: the owner trusts the initializer, and no accessors are held across the call
+        unsafe { super::delegate(init, data) }
+            .map(|_| ())
+            .map_err(DiamondError::Revert)
+    }
+
+    fn only_owner(&self) -> Result<(), ProxyError> {
+        let sender = msg::sender();
+        if sender != self.owner.get() {
+            return Err(ProxyError::UnauthorizedAccount(sender));
+        }
+        Ok(())
+    }
+
+    fn set_owner(&mut self, new_owner: Address) {
+        evm::log(OwnershipTransferred {
+            previous_owner: self.owner.get(),
+            new_owner,
+        });
+        self.owner.set(new_owner);
+    }
+}
+
+/// The bookkeeping behind [`Diamond::cut`], kept apart from storage so that it can be tested.
+///
+/// Each facet lists its selectors, and facets with at least one selector are listed too. Removals
+/// move the last item of a list into the removed item's place, so each item records its index.
+trait Bookkeeping {
+    /// Gets the facet handling a selector, and the selector's index among the facet's.
+    fn selector(&self, selector: FixedBytes<4>) -> (Address, usize);
+    /// Sets the facet handling a selector and its index, deleting it if the facet is zero.
+    fn set_selector(&mut self, selector: FixedBytes<4>, facet: Address, index: usize);
+
+    /// Gets the number of selectors a facet handles.
+    fn selector_count(&self, facet: Address) -> usize;
+    /// Gets a facet's selector at the given index.
+    fn facet_selector(&self, facet: Address, index: usize) -> FixedBytes<4>;
+    /// Overwrites a facet's selector at the given index.
+    fn set_facet_selector(&mut self, facet: Address, index: usize, selector: FixedBytes<4>);
+    /// Appends a selector to a facet's.
+    fn push_selector(&mut self, facet: Address, selector: FixedBytes<4>);
+    /// Removes a facet's last selector.
+    fn pop_selector(&mut self, facet: Address);
+
+    /// Gets the number of facets.
+    fn facet_count(&self) -> usize;
+    /// Gets the facet at the given index.
+    fn facet(&self, index: usize) -> Address;
+    /// Overwrites the facet at the given index.
+    fn set_facet(&mut self, index: usize, facet: Address);
+    /// Appends a facet.
+    fn push_facet(&mut self, facet: Address);
+    /// Removes the last facet.
+    fn pop_facet(&mut self);
+    /// Gets one more than a facet's index, or zero if it isn't listed.
+    fn facet_position(&self, facet: Address) -> usize;
+    /// Sets one more than a facet's index, deleting it if zero.
+    fn set_facet_position(&mut self, facet: Address, position: usize);
+}
+
+impl Bookkeeping for Diamond {
+    fn selector(&self, selector: FixedBytes<4>) -> (Address, usize) {
+        let info = self.selector_facets.get(selector);
+        (info.facet.get(), info.position.get().to())
+    }
+
+    fn set_selector(&mut self, selector: FixedBytes<4>, facet: Address, index: usize) {
+        if facet.is_zero() {
+            return self.selector_facets.delete(selector);
+        }
+        let mut info = self.selector_facets.setter(selector);
+        info.facet.set(facet);
+        info.position.set(U32::from(index));
+    }
+
+    fn selector_count(&self, facet: Address) -> usize {
+        self.facet_selectors.get(facet).len()
+    }
+
+    fn facet_selector(&self, facet: Address, index: usize) -> FixedBytes<4> {
+        self.facet_selectors
+            .get(facet)
+            .get(index)
+            .unwrap_or_default()
+    }
+
+    fn set_facet_selector(&mut self, facet: Address, index: usize, selector: FixedBytes<4>) {
+        let mut selectors = self.facet_selectors.setter(facet);
+        if let Some(mut slot) = selectors.setter(index) {
+            slot.set(selector);
+        }
+    }
+
+    fn push_selector(&mut self, facet: Address, selector: FixedBytes<4>) {
+        self.facet_selectors.setter(facet).push(selector);
+    }
+
+    fn pop_selector(&mut self, facet: Address) {
+        self.facet_selectors.setter(facet).erase_last();
+    }
+
+    fn facet_count(&self) -> usize {
+        self.facets.len()
+    }
+
+    fn facet(&self, index: usize) -> Address {
+        self.facets.get(index).unwrap_or_default()
+    }
+
+    fn set_facet(&mut self, index: usize, facet: Address) {
+        if let Some(mut slot) = self.facets.setter(index) {
+            slot.set(facet);
+        }
+    }
+
+    fn push_facet(&mut self, facet: Address) {
+        self.facets.push(facet);
+    }
+
+    fn pop_facet(&mut self) {
+        self.facets.erase_last();
+    }
+
+    fn facet_position(&self, facet: Address) -> usize {
+        self.facet_positions.get(facet).to()
+    }
+
+    fn set_facet_position(&mut self, facet: Address, position: usize) {
+        match position {
+            0 => self.facet_positions.delete(facet),
+            _ => self.facet_positions.insert(facet, U256::from(position)),
+        }
+    }
+}
+
+/// Applies a cut's action to one of its selectors.
+fn cut_selector(
+    state: &mut impl Bookkeeping,
+    action: FacetCutAction,
+    facet: Address,
+    selector: FixedBytes<4>,
+) -> Result<(), DiamondError> {
+    let (old, _) = state.selector(selector);
+    match action {
+        FacetCutAction::Add if !old.is_zero() => {
+            return Err(DiamondError::SelectorExists(selector));
+        }
+        FacetCutAction::Replace | FacetCutAction::Remove if old.is_zero() => {
+            return Err(DiamondError::SelectorNotFound(selector));
+        }
+        FacetCutAction::Replace if old == facet => {
+            return Err(DiamondError::SameFacet(selector));
+        }
+        _ => {}
+    }
+    if !old.is_zero() {
+        remove_selector(state, old, selector);
+    }
+    if action != FacetCutAction::Remove {
+        add_selector(state, facet, selector);
+    }
+    Ok(())
+}
+
+fn add_selector(state: &mut impl Bookkeeping, facet: Address, selector: FixedBytes<4>) {
+    let index = state.selector_count(facet);
+    state.push_selector(facet, selector);
+    state.set_selector(selector, facet, index);
+
+    if state.facet_position(facet) == 0 {
+        state.push_facet(facet);
+        let position = state.facet_count();
+        state.set_facet_position(facet, position);
+    }
+}
+
+fn remove_selector(state: &mut impl Bookkeeping, facet: Address, selector: FixedBytes<4>) {
+    let (_, index) = state.selector(selector);
+    state.set_selector(selector, Address::ZERO, 0);
+
+    // move the last selector into the removed one's place
+    let last = state.selector_count(facet) - 1;
+    if index != last {
+        let moved = state.facet_selector(facet, last);
+        state.set_facet_selector(facet, index, moved);
+        state.set_selector(moved, facet, index);
+    }
+    state.pop_selector(facet);
+    if last != 0 {
+        return;
+    }
+
+    // the facet has no more selectors, so move the last facet into its place
+    let index = state.facet_position(facet) - 1;
+    let last = state.facet_count() - 1;
+    if index != last {
+        let moved = state.facet(last);
+        state.set_facet(index, moved);
+        state.set_facet_position(moved, index + 1);
+    }
+    state.pop_facet();
+    state.set_facet_position(facet, 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cut_selector, selectors, Bookkeeping, Diamond, DiamondError, FacetCutAction};
+    use crate::abi::Bytes;
+    use alloc::{collections::BTreeMap, vec, vec::Vec};
+    use alloy_primitives::{Address, FixedBytes};
+
+    /// A diamond's bookkeeping, kept in memory.
+    #[derive(Default)]
+    struct Model {
+        selectors: BTreeMap<FixedBytes<4>, (Address, usize)>,
+        facet_selectors: BTreeMap<Address, Vec<FixedBytes<4>>>,
+        facets: Vec<Address>,
+        positions: BTreeMap<Address, usize>,
+    }
+
+    impl Bookkeeping for Model {
+        fn selector(&self, selector: FixedBytes<4>) -> (Address, usize) {
+            self.selectors.get(&selector).copied().unwrap_or_default()
+        }
+
+        fn set_selector(&mut self, selector: FixedBytes<4>, facet: Address, index: usize) {
+            match facet.is_zero() {
+                true => self.selectors.remove(&selector),
+                false => self.selectors.insert(selector, (facet, index)),
+            };
+        }
+
+        fn selector_count(&self, facet: Address) -> usize {
+            self.facet_selectors.get(&facet).map_or(0, Vec::len)
+        }
+
+        fn facet_selector(&self, facet: Address, index: usize) -> FixedBytes<4> {
+            self.facet_selectors[&facet][index]
+        }
+
+        fn set_facet_selector(&mut self, facet: Address, index: usize, selector: FixedBytes<4>) {
+            self.facet_selectors.get_mut(&facet).unwrap()[index] = selector;
+        }
+
+        fn push_selector(&mut self, facet: Address, selector: FixedBytes<4>) {
+            self.facet_selectors
+                .entry(facet)
+                .or_default()
+                .push(selector);
+        }
+
+        fn pop_selector(&mut self, facet: Address) {
+            self.facet_selectors.get_mut(&facet).unwrap().pop();
+        }
+
+        fn facet_count(&self) -> usize {
+            self.facets.len()
+        }
+
+        fn facet(&self, index: usize) -> Address {
+            self.facets[index]
+        }
+
+        fn set_facet(&mut self, index: usize, facet: Address) {
+            self.facets[index] = facet;
+        }
+
+        fn push_facet(&mut self, facet: Address) {
+            self.facets.push(facet);
+        }
+
+        fn pop_facet(&mut self) {
+            self.facets.pop();
+        }
+
+        fn facet_position(&self, facet: Address) -> usize {
+            self.positions.get(&facet).copied().unwrap_or_default()
+        }
+
+        fn set_facet_position(&mut self, facet: Address, position: usize) {
+            match position {
+                0 => self.positions.remove(&facet),
+                _ => self.positions.insert(facet, position),
+            };
+        }
+    }
+
+    impl Model {
+        /// Applies a cut, with selectors abbreviated to their last byte.
+        fn cut(&mut self, action: FacetCutAction, facet: Address, selectors: &[u8]) {
+            for &selector in selectors {
+                cut_selector(self, action, facet, selector_of(selector)).unwrap();
+            }
+        }
+
+        /// Checks that every index agrees with its list, returning each facet's selectors.
+        fn facets(&self) -> Vec<(Address, Vec<u8>)> {
+            let mut facets: Vec<(Address, Vec<u8>)> = vec![];
+            for (i, &facet) in self.facets.iter().enumerate() {
+                assert_eq!(self.positions[&facet], i + 1);
+                let selectors = &self.facet_selectors[&facet];
+                assert!(!selectors.is_empty());
+                for (j, &selector) in selectors.iter().enumerate() {
+                    assert_eq!(self.selectors[&selector], (facet, j));
+                }
+                facets.push((facet, selectors.iter().map(|s| s[3]).collect()));
+            }
+            let count: usize = facets.iter().map(|(_, s)| s.len()).sum();
+            assert_eq!(self.selectors.len(), count);
+            assert_eq!(self.positions.len(), self.facets.len());
+            facets
+        }
+    }
+
+    fn selector_of(byte: u8) -> FixedBytes<4> {
+        FixedBytes([0, 0, 0, byte])
+    }
+
+    #[test]
+    fn test_bookkeeping() {
+        use FacetCutAction::*;
+        let (a, b, c) = (
+            Address::repeat_byte(10),
+            Address::repeat_byte(11),
+            Address::repeat_byte(12),
+        );
+        let zero = Address::ZERO;
+
+        let mut model = Model::default();
+        model.cut(Add, a, &[1, 2, 3]);
+        model.cut(Add, b, &[4]);
+        model.cut(Add, c, &[5, 6]);
+        let facets = model.facets();
+        assert_eq!(facets, [(a, vec![1, 2, 3]), (b, vec![4]), (c, vec![5, 6])]);
+
+        // removing the last selector moves nothing
+        model.cut(Remove, zero, &[3]);
+        assert_eq!(
+            model.facets(),
+            [(a, vec![1, 2]), (b, vec![4]), (c, vec![5, 6])]
+        );
+
+        // removing another moves the last into its place
+        model.cut(Remove, zero, &[1]);
+        assert_eq!(
+            model.facets(),
+            [(a, vec![2]), (b, vec![4]), (c, vec![5, 6])]
+        );
+
+        // replacing moves the selector to the end of the other facet's, and since it was the
+        // first facet's last, the last facet moves into its place
+        model.cut(Replace, c, &[2]);
+        assert_eq!(model.facets(), [(c, vec![5, 6, 2]), (b, vec![4])]);
+
+        // removing the last facet's last selector moves nothing
+        model.cut(Remove, zero, &[4]);
+        assert_eq!(model.facets(), [(c, vec![5, 6, 2])]);
+
+        // facets may come back
+        model.cut(Replace, b, &[5]);
+        model.cut(Add, a, &[1]);
+        assert_eq!(
+            model.facets(),
+            [(c, vec![2, 6]), (b, vec![5]), (a, vec![1])]
+        );
+
+        let mut cut = |action, facet, selector| cut_selector(&mut model, action, facet, selector);
+        let (one, nine) = (selector_of(1), selector_of(9));
+        assert_eq!(cut(Add, b, one), Err(DiamondError::SelectorExists(one)));
+        assert_eq!(cut(Replace, a, one), Err(DiamondError::SameFacet(one)));
+        assert_eq!(
+            cut(Replace, a, nine),
+            Err(DiamondError::SelectorNotFound(nine))
+        );
+        assert_eq!(
+            cut(Remove, zero, nine),
+            Err(DiamondError::SelectorNotFound(nine))
+        );
+    }
+
+    #[test]
+    fn test_diamond_abi() {
+        type Cut = (Address, u8, Vec<FixedBytes<4>>);
+        let selector = crate::function_selector!("diamondCut", Vec<Cut>, Address, Bytes);
+        assert_eq!(u32::from_be_bytes(selector), 0x1f931c1c);
+
+        assert!(matches!(
+            FacetCutAction::try_from(2),
+            Ok(FacetCutAction::Remove)
+        ));
+        assert!(FacetCutAction::try_from(3).is_err());
+
+        let immutable = selectors::<Diamond>();
+        assert_eq!(immutable.len(), 7);
+        assert!(immutable.contains(&FixedBytes::new(selector)));
+        assert!(immutable.contains(&FixedBytes::new(0x7a0ed627_u32.to_be_bytes()))); // facets()
+
+        let encoded: Vec<u8> = DiamondError::InvalidAction(3).into();
+        assert_eq!(
+            encoded[..4],
+            crate::function_selector!("DiamondInvalidAction", u8)
+        );
+    }
+}
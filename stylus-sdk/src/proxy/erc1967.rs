@@ -0,0 +1,26 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::impl_unit_storage;
+use crate::{prelude::*, ArbResult};
+
+/// An [`EIP-1967`] proxy that delegates all unmatched calls to its implementation.
+///
+/// Use via `#[inherit]`, providing methods of your own to set the implementation and admin.
+///
+/// [`EIP-1967`]: https://eips.ethereum.org/EIPS/eip-1967
+#[derive(Debug, Default)]
+pub struct Erc1967Proxy;
+
+impl_unit_storage!(Erc1967Proxy);
+
+#[public]
+impl Erc1967Proxy {
+    /// Delegates the call to the implementation, returning whatever it does.
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, input: &[u8]) -> ArbResult {
+        // safety: the proxy trusts its implementation, and holds no accessors across the call
+        unsafe { super::delegate(super::implementation(), input) }
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Upgradeable contracts via [`EIP-1967`] proxies.
+//!
+//! A proxy stores the address of its implementation in a well-known slot, delegating every call
+//! it receives to that implementation via its `#[fallback]`. This module provides typed accessors
+//! for the [`EIP-1967`] implementation, admin, and beacon slots, along with ready-made contracts.
+//!
+//! - [`Erc1967Proxy`] delegates to its implementation, leaving upgrades to it.
+//! - [`UupsUpgradeable`] is a mixin for implementations that upgrade the proxies using them.
+//! - [`TransparentUpgradeableProxy`] lets only its admin upgrade it, and only others reach the
+//!   implementation.
+//! - [`BeaconProxy`] delegates to the implementation held by an [`UpgradeableBeacon`], which
+//!   many proxies may share.
+//! - [`Diamond`](diamond::Diamond) delegates each selector to the facet handling it, as in
+//!   [`EIP-2535`](diamond).
+//!
+//! Each follows OpenZeppelin's slot and event semantics, so existing tooling recognizes them.
+//!
+//! ```ignore
+//! use stylus_sdk::{prelude::*, proxy::{self, Erc1967Proxy}};
+//!
+//! #[storage]
+//! #[entrypoint]
+//! pub struct Proxy {
+//!     #[borrow]
+//!     erc1967: Erc1967Proxy,
+//! }
+//!
+//! #[public]
+//! #[inherit(Erc1967Proxy)]
+//! impl Proxy {
+//!     pub fn init(&mut self, implementation: Address, data: Bytes) -> Result<(), Vec<u8>> {
+//!         if proxy::implementation() != Address::ZERO {
+//!             return Err(vec![]);
+//!         }
+//!         proxy::change_admin(msg::sender())?;
+//!         Ok(proxy::upgrade_to_and_call(implementation, &data)?)
+//!     }
+//! }
+//! ```
+//!
+//! Note that the proxy's own methods take precedence over those of the implementation.
+//!
+//! [`EIP-1967`]: https://eips.ethereum.org/EIPS/eip-1967
+
+use crate::{
+    call::RawCall,
+    evm, msg,
+    storage::{GlobalStorage, StorageCache},
+    types::AddressVM,
+    ArbResult,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{uint, Address, B256, U256};
+use alloy_sol_types::{sol, SolError};
+
+#[cfg(feature = "reentrant")]
+use crate::storage::Storage;
+
+pub use beacon::{BeaconProxy, UpgradeableBeacon};
+pub use erc1967::Erc1967Proxy;
+pub use transparent::TransparentUpgradeableProxy;
+pub use uups::{AdminOnly, AuthorizeUpgrade, UupsUpgradeable};
+
+mod beacon;
+pub mod diamond;
+mod erc1967;
+mod transparent;
+mod uups;
+
+/// The slot holding the implementation address.
+/// This is `keccak256("eip1967.proxy.implementation") - 1`.
+pub const IMPLEMENTATION_SLOT: U256 =
+    uint!(0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc_U256);
+
+/// The slot holding the admin address, which is `keccak256("eip1967.proxy.admin") - 1`.
+pub const ADMIN_SLOT: U256 =
+    uint!(0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103_U256);
+
+/// The slot holding the beacon address, which is `keccak256("eip1967.proxy.beacon") - 1`.
+pub const BEACON_SLOT: U256 =
+    uint!(0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50_U256);
+
+sol! {
+    /// Emitted when the implementation is upgraded.
+    event Upgraded(address indexed implementation);
+
+    /// Emitted when the admin changes.
+    event AdminChanged(address previous_admin, address new_admin);
+
+    /// Emitted when the beacon is changed.
+    event BeaconUpgraded(address indexed beacon);
+
+    /// Emitted when a beacon's owner changes.
+    event OwnershipTransferred(address indexed previous_owner, address indexed new_owner);
+
+    /// The implementation isn't a contract, or doesn't support upgrades.
+    #[allow(missing_docs)]
+    error ERC1967InvalidImplementation(address implementation);
+
+    /// The admin is the zero address.
+    #[allow(missing_docs)]
+    error ERC1967InvalidAdmin(address admin);
+
+    /// The beacon, or the implementation it points to, isn't a contract.
+    #[allow(missing_docs)]
+    error ERC1967InvalidBeacon(address beacon);
+
+    /// Value was sent during an upgrade that doesn't call the implementation.
+    #[allow(missing_docs)]
+    error ERC1967NonPayable();
+
+    /// The call isn't happening through a proxy.
+    #[allow(missing_docs)]
+    error UUPSUnauthorizedCallContext();
+
+    /// The new implementation reports a different implementation slot.
+    #[allow(missing_docs)]
+    error UUPSUnsupportedProxiableUUID(bytes32 slot);
+
+    /// The caller isn't allowed to perform the operation.
+    #[allow(missing_docs)]
+    error ProxyUnauthorizedAccount(address account);
+
+    /// The admin of a transparent proxy called something other than an upgrade.
+    #[allow(missing_docs)]
+    error ProxyDeniedAdminAccess();
+
+    /// A beacon's new implementation isn't a contract.
+    #[allow(missing_docs)]
+    error BeaconInvalidImplementation(address implementation);
+}
+
+/// Represents the ways an operation on a proxy may fail.
+#[derive(Debug, PartialEq)]
+pub enum ProxyError {
+    /// The implementation isn't a contract, or doesn't support upgrades.
+    InvalidImplementation(Address),
+    /// The admin is the zero address.
+    InvalidAdmin(Address),
+    /// The beacon, or the implementation it points to, isn't a contract.
+    InvalidBeacon(Address),
+    /// Value was sent during an upgrade that doesn't call the implementation.
+    NonPayable,
+    /// The call isn't happening through a proxy.
+    UnauthorizedCallContext,
+    /// The new implementation reports a different implementation slot.
+    UnsupportedProxiableUuid(B256),
+    /// The caller isn't allowed to perform the operation.
+    UnauthorizedAccount(Address),
+    /// The admin of a transparent proxy called something other than an upgrade.
+    DeniedAdminAccess,
+    /// A beacon's new implementation isn't a contract.
+    InvalidBeaconImplementation(Address),
+    /// Revert data returned by the implementation when initializing it.
+    Revert(Vec<u8>),
+}
+
+impl From<ProxyError> for Vec<u8> {
+    fn from(err: ProxyError) -> Vec<u8> {
+        use ProxyError::*;
+        match err {
+            InvalidImplementation(implementation) => {
+                ERC1967InvalidImplementation { implementation }.abi_encode()
+            }
+            InvalidAdmin(admin) => ERC1967InvalidAdmin { admin }.abi_encode(),
+            InvalidBeacon(beacon) => ERC1967InvalidBeacon { beacon }.abi_encode(),
+            NonPayable => ERC1967NonPayable {}.abi_encode(),
+            UnauthorizedCallContext => UUPSUnauthorizedCallContext {}.abi_encode(),
+            UnsupportedProxiableUuid(slot) => UUPSUnsupportedProxiableUUID { slot }.abi_encode(),
+            UnauthorizedAccount(account) => ProxyUnauthorizedAccount { account }.abi_encode(),
+            DeniedAdminAccess => ProxyDeniedAdminAccess {}.abi_encode(),
+            InvalidBeaconImplementation(implementation) => {
+                BeaconInvalidImplementation { implementation }.abi_encode()
+            }
+            Revert(data) => data,
+        }
+    }
+}
+
+/// Gets the current implementation address.
+pub fn implementation() -> Address {
+    get_address(IMPLEMENTATION_SLOT)
+}
+
+/// Gets the current admin address.
+pub fn admin() -> Address {
+    get_address(ADMIN_SLOT)
+}
+
+/// Gets the current beacon address.
+pub fn beacon() -> Address {
+    get_address(BEACON_SLOT)
+}
+
+/// Upgrades to a new implementation, emitting an [`Upgraded`] event.
+///
+/// If `data` is nonempty, the new implementation is delegate called with it, which is typically
+/// used to initialize its storage. Otherwise, no value may be sent with the call.
+pub fn upgrade_to_and_call(new_implementation: Address, data: &[u8]) -> Result<(), ProxyError> {
+    if !new_implementation.has_code() {
+        return Err(ProxyError::InvalidImplementation(new_implementation));
+    }
+    set_address(IMPLEMENTATION_SLOT, new_implementation);
+    evm::log(Upgraded {
+        implementation: new_implementation,
+    });
+    initialize(new_implementation, data)
+}
+
+/// Changes the admin, emitting an [`AdminChanged`] event.
+pub fn change_admin(new_admin: Address) -> Result<(), ProxyError> {
+    if new_admin.is_zero() {
+        return Err(ProxyError::InvalidAdmin(new_admin));
+    }
+    evm::log(AdminChanged {
+        previous_admin: admin(),
+        new_admin,
+    });
+    set_address(ADMIN_SLOT, new_admin);
+    Ok(())
+}
+
+/// Changes the beacon, emitting a [`BeaconUpgraded`] event.
+///
+/// If `data` is nonempty, the beacon's implementation is delegate called with it, which is
+/// typically used to initialize its storage. Otherwise, no value may be sent with the call.
+pub fn upgrade_beacon_to_and_call(new_beacon: Address, data: &[u8]) -> Result<(), ProxyError> {
+    if !new_beacon.has_code() {
+        return Err(ProxyError::InvalidBeacon(new_beacon));
+    }
+    let implementation = beacon_implementation(new_beacon);
+    if !implementation.is_some_and(|implementation| implementation.has_code()) {
+        return Err(ProxyError::InvalidBeacon(new_beacon));
+    }
+    set_address(BEACON_SLOT, new_beacon);
+    evm::log(BeaconUpgraded { beacon: new_beacon });
+    initialize(implementation.unwrap_or_default(), data)
+}
+
+/// Asks a beacon for its current implementation via `implementation()`.
+pub fn beacon_implementation(beacon: Address) -> Option<Address> {
+    let selector = crate::function_selector!("implementation");
+
+    let call = RawCall::new_static();
+    #[cfg(feature = "reentrant")]
+    let call = call.flush_storage_cache(); // the beacon may read back our state
+
+    // safety: static calls can't modify storage
+    #[allow(unused_unsafe)]
+    let returned = unsafe { call.call(beacon, &selector) };
+    let word = returned.ok()?;
+    let word = word.get(..32)?;
+    (word[..12] == [0; 12]).then(|| Address::from_slice(&word[12..]))
+}
+
+/// Delegates a call to the implementation, returning whatever it does.
+///
+/// # Safety
+///
+/// A delegate call must trust the other contract to uphold safety requirements, since it may
+/// arbitrarily change storage. Accessors must not be held across the call.
+pub unsafe fn delegate(implementation: Address, calldata: &[u8]) -> ArbResult {
+    #[cfg(feature = "reentrant")]
+    Storage::clear(); // clear the storage to persist changes, invalidating the cache
+
+    RawCall::new_delegate().call(implementation, calldata)
+}
+
+fn initialize(implementation: Address, data: &[u8]) -> Result<(), ProxyError> {
+    if data.is_empty() {
+        if msg::value() != U256::ZERO {
+            return Err(ProxyError::NonPayable);
+        }
+        return Ok(());
+    }
+    unsafe { delegate(implementation, data) }
+        .map(|_| ())
+        .map_err(ProxyError::Revert)
+}
+
+fn get_address(slot: U256) -> Address {
+    Address::from_word(StorageCache::get_word(slot))
+}
+
+fn set_address(slot: U256, address: Address) {
+    // safety: the EIP-1967 slots are pseudorandom, so no accessors alias them
+    unsafe { StorageCache::set_word(slot, address.into_word()) }
+}
+
+/// Implements [`StorageType`](crate::storage::StorageType) for proxy contracts without fields of
+/// their own, which take up no space in the structs containing them.
+macro_rules! impl_unit_storage {
+    ($name:ident) => {
+        impl $crate::storage::StorageType for $name {
+            type Wraps<'a> = $crate::storage::StorageGuard<'a, Self> where Self: 'a;
+            type WrapsMut<'a> = $crate::storage::StorageGuardMut<'a, Self> where Self: 'a;
+
+            const SLOT_BYTES: usize = 0;
+            const REQUIRED_SLOTS: usize = 0;
+
+            unsafe fn new(_slot: $crate::alloy_primitives::U256, _offset: u8) -> Self {
+                Self
+            }
+
+            fn load<'s>(self) -> Self::Wraps<'s> {
+                $crate::storage::StorageGuard::new(self)
+            }
+
+            fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+                $crate::storage::StorageGuardMut::new(self)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_unit_storage;
+
+#[cfg(test)]
+mod tests {
+    use super::{ProxyError, ADMIN_SLOT, BEACON_SLOT, IMPLEMENTATION_SLOT};
+    use crate::crypto::keccak;
+    use alloc::vec::Vec;
+    use alloy_primitives::{Address, U256};
+
+    #[test]
+    fn test_slots() {
+        let slot = |name: &str| U256::from_be_bytes(keccak(name).0) - U256::from(1);
+        assert_eq!(IMPLEMENTATION_SLOT, slot("eip1967.proxy.implementation"));
+        assert_eq!(ADMIN_SLOT, slot("eip1967.proxy.admin"));
+        assert_eq!(BEACON_SLOT, slot("eip1967.proxy.beacon"));
+
+        let encoded: Vec<u8> = ProxyError::InvalidAdmin(Address::ZERO).into();
+        assert_eq!(
+            encoded[..4],
+            crate::function_selector!("ERC1967InvalidAdmin", Address)
+        );
+        let encoded: Vec<u8> = ProxyError::Revert(alloc::vec![1, 2]).into();
+        assert_eq!(encoded, [1, 2]);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{impl_unit_storage, ProxyError};
+use crate::{msg, prelude::*, ArbResult};
+use alloc::vec::Vec;
+use alloy_sol_types::{sol, SolCall};
+
+sol! {
+    #[allow(missing_docs)]
+    function upgradeToAndCall(address new_implementation, bytes data);
+}
+
+/// An [`EIP-1967`] proxy whose admin may only upgrade it, and whose other callers may only reach
+/// the implementation.
+///
+/// Calls from the [`admin`](super::admin) must be to `upgradeToAndCall(address,bytes)`, which
+/// the proxy handles itself. All other calls are delegated to the implementation, even those
+/// sharing the selector, so that the proxy never shadows the implementation's methods.
+///
+/// [`EIP-1967`]: https://eips.ethereum.org/EIPS/eip-1967
+#[derive(Debug, Default)]
+pub struct TransparentUpgradeableProxy;
+
+impl_unit_storage!(TransparentUpgradeableProxy);
+
+#[public]
+impl TransparentUpgradeableProxy {
+    /// Upgrades the proxy when called by the admin, and otherwise delegates to the implementation.
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, input: &[u8]) -> ArbResult {
+        if msg::sender() != super::admin() {
+            // safety: the proxy trusts its implementation, and holds no accessors across the call
+            return unsafe { super::delegate(super::implementation(), input) };
+        }
+        let Ok(call) = upgradeToAndCallCall::abi_decode(input, true) else {
+            return Err(ProxyError::DeniedAdminAccess.into());
+        };
+        super::upgrade_to_and_call(call.new_implementation, &call.data)?;
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::upgradeToAndCallCall;
+    use crate::abi::Bytes;
+    use alloy_primitives::Address;
+    use alloy_sol_types::SolCall;
+
+    #[test]
+    fn test_upgrade_selector() {
+        let selector = crate::function_selector!("upgradeToAndCall", Address, Bytes);
+        assert_eq!(upgradeToAndCallCall::SELECTOR, selector);
+
+        let call = upgradeToAndCallCall {
+            new_implementation: Address::repeat_byte(1),
+            data: alloc::vec![2, 3].into(),
+        };
+        let decoded = upgradeToAndCallCall::abi_decode(&call.abi_encode(), true).unwrap();
+        assert_eq!(decoded.new_implementation, call.new_implementation);
+        assert_eq!(decoded.data, call.data);
+        assert!(upgradeToAndCallCall::abi_decode(&selector, true).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{impl_unit_storage, ProxyError, IMPLEMENTATION_SLOT};
+use crate::{
+    abi::Bytes,
+    call::RawCall,
+    msg,
+    prelude::*,
+    storage::{StorageGuard, StorageGuardMut},
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, U256};
+use core::{fmt, marker::PhantomData};
+
+/// Decides who may upgrade a [`UupsUpgradeable`] implementation, like Solidity's
+/// `_authorizeUpgrade`.
+///
+/// To consult the program's own state, implement this for its [`TopLevelStorage`] and inherit
+/// `UupsUpgradeable<Self>`. The check then runs on a fresh accessor to the program's storage.
+pub trait AuthorizeUpgrade: StorageType {
+    /// Returns an error unless the caller may upgrade to `new_implementation`.
+    fn authorize_upgrade(&self, new_implementation: Address) -> Result<(), Vec<u8>>;
+}
+
+/// Lets only the proxy's [`admin`](super::admin) upgrade, which [`UupsUpgradeable`] does by
+/// default.
+#[derive(Debug, Default)]
+pub struct AdminOnly;
+
+impl_unit_storage!(AdminOnly);
+
+impl AuthorizeUpgrade for AdminOnly {
+    fn authorize_upgrade(&self, _new_implementation: Address) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        if sender != super::admin() {
+            return Err(ProxyError::UnauthorizedAccount(sender).into());
+        }
+        Ok(())
+    }
+}
+
+/// An implementation mixin for [`UUPS`] proxies, which upgrade via their implementation.
+///
+/// Use via `#[inherit]` to expose `proxiableUUID()` and `upgradeToAndCall(address,bytes)`.
+/// Who may upgrade is up to `A`, which by default is only the proxy's [`admin`](super::admin).
+///
+/// ```ignore
+/// #[storage]
+/// #[entrypoint]
+/// pub struct Token {
+///     owner: StorageAddress,
+///     #[borrow]
+///     uups: UupsUpgradeable<Token>,
+/// }
+///
+/// #[public]
+/// #[inherit(UupsUpgradeable<Token>)]
+/// impl Token {}
+///
+/// impl AuthorizeUpgrade for Token {
+///     fn authorize_upgrade(&self, _new_implementation: Address) -> Result<(), Vec<u8>> {
+///         match msg::sender() == self.owner.get() {
+///             true => Ok(()),
+///             false => Err(b"not the owner".to_vec()),
+///         }
+///     }
+/// }
+/// ```
+///
+/// Unlike in Solidity, a Stylus program can't know its own address while being delegate called.
+/// Instead, calls are deemed to come through a proxy when the implementation slot is set, which
+/// is never the case in an implementation's own storage.
+///
+/// [`UUPS`]: https://eips.ethereum.org/EIPS/eip-1822
+pub struct UupsUpgradeable<A = AdminOnly>(PhantomData<A>);
+
+impl<A> Default for UupsUpgradeable<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A> fmt::Debug for UupsUpgradeable<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UupsUpgradeable")
+    }
+}
+
+impl<A> StorageType for UupsUpgradeable<A> {
+    type Wraps<'a> = StorageGuard<'a, Self> where Self: 'a;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self> where Self: 'a;
+
+    const SLOT_BYTES: usize = 0;
+    const REQUIRED_SLOTS: usize = 0;
+
+    unsafe fn new(_slot: U256, _offset: u8) -> Self {
+        Self::default()
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+#[public]
+impl<A: AuthorizeUpgrade> UupsUpgradeable<A> {
+    /// Returns the [`IMPLEMENTATION_SLOT`], showing that this implementation supports upgrades.
+    /// Reverts when called through a proxy, which prevents upgrading to a proxy.
+    #[selector(name = "proxiableUUID")]
+    pub fn proxiable_uuid(&self) -> Result<B256, Vec<u8>> {
+        if Self::delegated() {
+            return Err(ProxyError::UnauthorizedCallContext.into());
+        }
+        Ok(IMPLEMENTATION_SLOT.into())
+    }
+
+    /// Upgrades the proxy to a new implementation, delegate calling it with `data` if nonempty.
+    /// The upgrade must be allowed by [`AuthorizeUpgrade`].
+    #[payable]
+    pub fn upgrade_to_and_call(
+        &mut self,
+        new_implementation: Address,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        if !Self::delegated() {
+            return Err(ProxyError::UnauthorizedCallContext.into());
+        }
+
+        // safety: the accessor is dropped before storage is next modified
+        let authority = unsafe { A::new(U256::ZERO, 0) };
+        authority.authorize_upgrade(new_implementation)?;
+        drop(authority);
+
+        let selector = crate::function_selector!("proxiableUUID");
+
+        let call = RawCall::new_static();
+        #[cfg(feature = "reentrant")]
+        let call = call.flush_storage_cache(); // the implementation may read back our state
+
+        // safety: static calls can't modify storage
+        #[allow(unused_unsafe)]
+        let uuid = unsafe { call.call(new_implementation, &selector) };
+        let uuid = match uuid.ok().filter(|uuid| uuid.len() == 32) {
+            Some(uuid) => B256::from_slice(&uuid),
+            None => return Err(ProxyError::InvalidImplementation(new_implementation).into()),
+        };
+        if uuid != B256::from(IMPLEMENTATION_SLOT) {
+            return Err(ProxyError::UnsupportedProxiableUuid(uuid).into());
+        }
+        Ok(super::upgrade_to_and_call(new_implementation, &data)?)
+    }
+}
+
+impl<A> UupsUpgradeable<A> {
+    /// Whether the current call is happening through a proxy.
+    fn delegated() -> bool {
+        super::implementation() != Address::ZERO
+    }
+}
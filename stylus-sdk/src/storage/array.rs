@@ -19,6 +19,8 @@ impl<S: StorageType, const N: usize> StorageType for StorageArray<S, N> {
 
     unsafe fn new(slot: U256, offset: u8) -> Self {
         debug_assert!(offset == 0);
+        let () = Self::NOT_NAMESPACED;
+        let () = Self::NOT_TRANSIENT;
         Self {
             slot,
             marker: PhantomData,
@@ -35,6 +37,9 @@ impl<S: StorageType, const N: usize> StorageType for StorageArray<S, N> {
 }
 
 impl<S: StorageType, const N: usize> StorageArray<S, N> {
+    const NOT_NAMESPACED: () = super::assert_not_namespaced::<S>();
+    const NOT_TRANSIENT: () = super::assert_not_transient::<S>();
+
     /// Gets the number of elements stored.
     ///
     /// Although this type will always have the same length, this method is still provided for
@@ -113,6 +118,8 @@ impl<S: StorageType, const N: usize> StorageArray<S, N> {
 
     /// Required slots for the storage array.
     const fn required_slots() -> usize {
+        let () = Self::NOT_NAMESPACED;
+        let () = Self::NOT_TRANSIENT;
         let reserved = N * S::REQUIRED_SLOTS;
         let density = Self::density();
         let packed = (N + density - 1) / density; // ceil division for packed items.
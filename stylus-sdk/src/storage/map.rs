@@ -1,7 +1,7 @@
 // Copyright 2023-2024, Offchain Labs, Inc.
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
-use crate::crypto;
+use crate::crypto::{self, Keccak};
 
 use super::{Erase, SimpleStorageType, StorageGuard, StorageGuardMut, StorageType};
 use alloc::{string::String, vec::Vec};
@@ -24,6 +24,8 @@ where
 
     unsafe fn new(slot: U256, offset: u8) -> Self {
         debug_assert!(offset == 0);
+        let () = Self::NOT_NAMESPACED;
+        let () = Self::NOT_TRANSIENT;
         Self {
             slot,
             marker: PhantomData,
@@ -44,15 +46,45 @@ where
     K: StorageKey,
     V: StorageType,
 {
+    const NOT_NAMESPACED: () = super::assert_not_namespaced::<V>();
+    const NOT_TRANSIENT: () = super::assert_not_transient::<V>();
+
     /// Where in a word to access the wrapped value.
     const CHILD_OFFSET: u8 = 32 - V::SLOT_BYTES as u8;
 
+    /// Where in a transient word to access the wrapped value, if it's transient.
+    const TRANSIENT_CHILD_OFFSET: u8 = 32 - V::TRANSIENT_SLOT_BYTES as u8;
+
+    /// Creates a map whose values may be transient, as those of a [`TransientMap`] are.
+    ///
+    /// # Safety
+    ///
+    /// Aliases storage if two calls to the same slot occur within the same lifetime.
+    ///
+    /// [`TransientMap`]: super::TransientMap
+    pub(super) unsafe fn new_transient(slot: U256) -> Self {
+        let () = Self::NOT_NAMESPACED;
+        Self {
+            slot,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates an accessor to the value at the given slot.
+    ///
+    /// # Safety
+    ///
+    /// Aliases storage if two calls to the same slot occur within the same lifetime.
+    unsafe fn child(slot: U256) -> V {
+        V::new_with_transient(slot, Self::CHILD_OFFSET, slot, Self::TRANSIENT_CHILD_OFFSET)
+    }
+
     /// Gets an accessor to the element at the given key, or the zero-value if none is there.
     /// Note: the accessor is protected by a [`StorageGuard`], which restricts its lifetime
     /// to that of `&self`.
     pub fn getter(&self, key: K) -> StorageGuard<V> {
         let slot = key.to_slot(self.slot.into());
-        unsafe { StorageGuard::new(V::new(slot, Self::CHILD_OFFSET)) }
+        unsafe { StorageGuard::new(Self::child(slot)) }
     }
 
     /// Gets a mutable accessor to the element at the given key, or the zero-value is none is there.
@@ -60,7 +92,7 @@ where
     /// to that of `&mut self`.
     pub fn setter(&mut self, key: K) -> StorageGuardMut<V> {
         let slot = key.to_slot(self.slot.into());
-        unsafe { StorageGuardMut::new(V::new(slot, Self::CHILD_OFFSET)) }
+        unsafe { StorageGuardMut::new(Self::child(slot)) }
     }
 
     /// Gets the element at the given key, or the zero value if none is there.
@@ -87,8 +119,8 @@ where
         let slot = key.to_slot(self.slot.into());
         // intentionally alias so that we can erase after load
         unsafe {
-            let store = V::new(slot, Self::CHILD_OFFSET);
-            let mut alias = V::new(slot, Self::CHILD_OFFSET);
+            let store = Self::child(slot);
+            let mut alias = Self::child(slot);
             let prior = store.load();
             alias.set_by_wrapped(value);
             prior
@@ -101,8 +133,8 @@ where
         let slot = key.to_slot(self.slot.into());
         // intentionally alias so that we can erase after load
         unsafe {
-            let store = V::new(slot, Self::CHILD_OFFSET);
-            let mut alias = V::new(slot, Self::CHILD_OFFSET);
+            let store = Self::child(slot);
+            let mut alias = Self::child(slot);
             let value = store.load();
             alias.erase();
             value
@@ -159,9 +191,10 @@ impl<const N: usize> StorageKey for FixedBytes<N> {
 
 impl StorageKey for &[u8] {
     fn to_slot(&self, root: B256) -> U256 {
-        let mut vec = self.to_vec();
-        vec.extend(root);
-        crypto::keccak(vec).into()
+        let mut hasher = Keccak::with_capacity(self.len() + 32);
+        hasher.update(self);
+        hasher.update(root);
+        hasher.finalize().into()
     }
 }
 
@@ -18,9 +18,15 @@
 //!
 //! Storage Operations are also cached by default, ensuring that efficient usage is clean and auditable.
 //!
+//! Per-transaction state, like reentrancy locks and flash accounting, can instead use the much
+//! cheaper [`EIP-1153`] transient storage via types like [`TransientBool`] and [`TransientMap`].
+//! These are cleared at the end of every transaction, and can be declared alongside persistent
+//! types in `#[storage]` structs.
+//!
 //! For a walkthrough of this module's features, please see [The Feature Overview][overview].
 //!
 //! [overview]: https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#storage
+//! [`EIP-1153`]: https://eips.ethereum.org/EIPS/eip-1153
 
 use crate::hostio;
 use alloy_primitives::{Address, BlockHash, BlockNumber, FixedBytes, Signed, Uint, B256, U256};
@@ -34,12 +40,18 @@ pub use traits::{
     Erase, GlobalStorage, SimpleStorageType, StorageGuard, StorageGuardMut, StorageType,
     TopLevelStorage,
 };
+pub use transient::{
+    TransientAddress, TransientBool, TransientMap, TransientStorage, TransientStorageType,
+    TransientU128, TransientU16, TransientU256, TransientU32, TransientU64, TransientU8,
+    TransientUint,
+};
 pub use vec::StorageVec;
 
 mod array;
 mod bytes;
 mod map;
 mod traits;
+mod transient;
 mod vec;
 
 pub(crate) type Storage = StorageCache;
@@ -83,6 +95,69 @@ impl StorageCache {
     }
 }
 
+/// Computes the root slot of an [`ERC-7201`] namespace, which is
+/// `keccak256(keccak256(namespace) - 1) & ~0xff`.
+///
+/// This is what [`#[storage(namespace = "...")]`][storage] uses, and can be evaluated at compile
+/// time.
+///
+/// ```
+/// use stylus_sdk::{alloy_primitives::U256, storage::erc7201_slot};
+///
+/// const ROOT: U256 = erc7201_slot("example.main");
+/// assert_eq!(
+///     ROOT,
+///     "0x183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500".parse().unwrap(),
+/// );
+/// ```
+///
+/// [`ERC-7201`]: https://eips.ethereum.org/EIPS/eip-7201
+/// [storage]: macro@stylus_proc::storage
+pub const fn erc7201_slot(namespace: &str) -> U256 {
+    let mut hash = keccak_const::Keccak256::new()
+        .update(namespace.as_bytes())
+        .finalize();
+
+    // subtract one, borrowing as needed
+    let mut i = 32;
+    while i > 0 {
+        i -= 1;
+        if hash[i] > 0 {
+            hash[i] -= 1;
+            break;
+        }
+        hash[i] = 0xff;
+    }
+
+    let mut slot = keccak_const::Keccak256::new().update(&hash).finalize();
+    slot[31] = 0;
+    U256::from_be_bytes(slot)
+}
+
+/// Fails the build when collection elements of type `S` are namespaced.
+///
+/// Namespaced types ignore the slot they're given in [`StorageType::new`], so every element of a
+/// [`StorageVec`], [`StorageArray`], or [`StorageMap`] would alias the same root. Collections
+/// evaluate this in an associated `const`, which makes the assertion fire when monomorphized.
+pub(crate) const fn assert_not_namespaced<S: StorageType>() {
+    assert!(
+        S::NAMESPACE.is_none(),
+        "namespaced storage can't be the elements of a collection"
+    );
+}
+
+/// Fails the build when collection elements of type `S` have transient fields.
+///
+/// Persistent collections like [`StorageVec`], [`StorageArray`], and [`StorageMap`] only lay out
+/// their elements in persistent storage, so transient elements would overlap. Transient values
+/// instead belong in a [`TransientMap`].
+pub(crate) const fn assert_not_transient<S: StorageType>() {
+    assert!(
+        S::TRANSIENT_SLOT_BYTES == 0 && S::TRANSIENT_REQUIRED_SLOTS == 0,
+        "transient storage can't be the elements of a persistent collection"
+    );
+}
+
 /// Overwrites the value in a cell.
 #[inline]
 fn overwrite_cell<T>(cell: &mut OnceCell<T>, value: T) {
@@ -42,6 +42,26 @@ pub trait StorageType: Sized {
     /// For complex types requiring more than one inline word, set this to the total size.
     const REQUIRED_SLOTS: usize = 0;
 
+    /// The [`ERC-7201`] namespace the type is rooted at, if any. Namespaced types ignore the slot
+    /// they're given in [`StorageType::new`], and so take up no space in the structs containing
+    /// them. For the same reason, they can't be the elements of collections like [`StorageVec`].
+    ///
+    /// [`StorageVec`]: super::StorageVec
+    ///
+    /// [`ERC-7201`]: https://eips.ethereum.org/EIPS/eip-7201
+    const NAMESPACE: Option<&'static str> = None;
+
+    /// Like [`StorageType::SLOT_BYTES`], but for [`EIP-1153`] transient storage, which is laid out
+    /// separately from persistent storage. This is zero for types without transient fields, and
+    /// transient types set [`StorageType::SLOT_BYTES`] to zero in turn. Types with transient fields
+    /// can't be the elements of persistent collections like [`StorageVec`](super::StorageVec).
+    ///
+    /// [`EIP-1153`]: https://eips.ethereum.org/EIPS/eip-1153
+    const TRANSIENT_SLOT_BYTES: usize = 0;
+
+    /// Like [`StorageType::REQUIRED_SLOTS`], but for transient storage.
+    const TRANSIENT_REQUIRED_SLOTS: usize = 0;
+
     /// Where in persistent storage the type should live. Although useful for framework designers
     /// creating new storage types, most user programs shouldn't call this.
     /// Note: implementations will have to be `const` once [`generic_const_exprs`] stabilizes.
@@ -53,6 +73,23 @@ pub trait StorageType: Sized {
     /// [`generic_const_exprs`]: https://github.com/rust-lang/rust/issues/76560
     unsafe fn new(slot: U256, offset: u8) -> Self;
 
+    /// Like [`StorageType::new`], but also says where in transient storage the type should live.
+    /// Structs with both persistent and transient fields use this to lay out each separately.
+    /// By default, the transient slot is ignored.
+    ///
+    /// # Safety
+    ///
+    /// Aliases storage if two calls to the same slots and offsets occur within the same lifetime.
+    unsafe fn new_with_transient(
+        slot: U256,
+        offset: u8,
+        transient_slot: U256,
+        transient_offset: u8,
+    ) -> Self {
+        let _ = (transient_slot, transient_offset);
+        Self::new(slot, offset)
+    }
+
     /// Load the wrapped type, consuming the accessor.
     /// Note: most types have a `get` and/or `getter`, which don't consume `Self`.
     fn load<'s>(self) -> Self::Wraps<'s>
@@ -175,7 +212,8 @@ impl<'a, T: 'a> DerefMut for StorageGuardMut<'a, T> {
 }
 
 /// Trait for managing access to persistent storage.
-/// Implemented by the [`StorageCache`](super::StorageCache) type.
+/// Implemented by the [`StorageCache`](super::StorageCache) and
+/// [`TransientStorage`](super::TransientStorage) types.
 pub trait GlobalStorage {
     /// Retrieves `N ≤ 32` bytes from persistent storage, performing [`SLOAD`]'s only as needed.
     /// The bytes are read from slot `key`, starting `offset` bytes from the left.
@@ -0,0 +1,488 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{
+    Erase, GlobalStorage, SimpleStorageType, StorageGuard, StorageGuardMut, StorageKey, StorageMap,
+    StorageType,
+};
+use crate::hostio;
+use alloy_primitives::{Address, Uint, B256, U256};
+
+/// Global accessor to [`EIP-1153`] transient storage, which is cleared at the end of every
+/// transaction.
+///
+/// Unlike with [`StorageCache`](super::StorageCache), reads and writes go directly to the VM, so
+/// there is nothing to flush, and values changed by other calls in the transaction are always
+/// seen. As in Solidity, transient fields in `#[storage]` structs are assigned slots separately
+/// from persistent fields, so adding one never shifts the persistent fields that follow it.
+///
+/// Transient storage suits state that only matters within a transaction, such as a reentrancy
+/// lock for programs that opt into the `reentrant` feature.
+///
+/// ```no_run
+/// use stylus_sdk::{call::{self, Call}, prelude::*, storage::{StorageU256, TransientBool}};
+/// use stylus_sdk::alloy_primitives::{Address, U256};
+/// extern crate alloc;
+///
+/// #[storage]
+/// #[entrypoint]
+/// pub struct Vault {
+///     locked: TransientBool,
+///     balance: StorageU256,
+/// }
+///
+/// #[public]
+/// impl Vault {
+///     pub fn withdraw(&mut self, to: Address) -> Result<(), Vec<u8>> {
+///         if self.locked.get() {
+///             return Err(b"reentrant call".to_vec());
+///         }
+///         self.locked.set(true);
+///
+///         let amount = self.balance.get();
+///         self.balance.set(U256::ZERO);
+///         call::call(Call::new_in(self).value(amount), to, &[])?;
+///
+///         self.locked.set(false);
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// [`EIP-1153`]: https://eips.ethereum.org/EIPS/eip-1153
+pub struct TransientStorage;
+
+impl GlobalStorage for TransientStorage {
+    /// Retrieves a 32-byte EVM word from transient storage.
+    fn get_word(key: U256) -> B256 {
+        let mut data = B256::ZERO;
+        unsafe { hostio::transient_load_bytes32(B256::from(key).as_ptr(), data.as_mut_ptr()) };
+        data
+    }
+
+    /// Stores a 32-byte EVM word to transient storage.
+    ///
+    /// # Safety
+    ///
+    /// May alias storage.
+    unsafe fn set_word(key: U256, value: B256) {
+        hostio::transient_store_bytes32(B256::from(key).as_ptr(), value.as_ptr())
+    }
+}
+
+/// Marker trait for accessors that live entirely in transient storage.
+/// Only these may be the values of a [`TransientMap`].
+pub trait TransientStorageType: StorageType {}
+
+macro_rules! alias_ints {
+    ($($name:ident, $bits:expr, $limbs:expr;)*) => {
+        $(
+            #[doc = concat!("Accessor for a transient [`alloy_primitives::aliases::U", stringify!($bits), "`].")]
+            pub type $name = TransientUint<$bits, $limbs>;
+        )*
+    };
+}
+
+alias_ints! {
+    TransientU8, 8, 1;
+    TransientU16, 16, 1;
+    TransientU32, 32, 1;
+    TransientU64, 64, 1;
+    TransientU128, 128, 2;
+    TransientU256, 256, 4;
+}
+
+/// Accessor for a transient [`alloy_primitives::Uint`].
+#[derive(Debug)]
+pub struct TransientUint<const B: usize, const L: usize> {
+    slot: U256,
+    offset: u8,
+}
+
+impl<const B: usize, const L: usize> TransientUint<B, L> {
+    /// Gets the underlying [`alloy_primitives::Uint`] in transient storage.
+    pub fn get(&self) -> Uint<B, L> {
+        unsafe { TransientStorage::get_uint(self.slot, self.offset.into()) }
+    }
+
+    /// Sets the underlying [`alloy_primitives::Uint`] in transient storage.
+    pub fn set(&mut self, value: Uint<B, L>) {
+        unsafe { TransientStorage::set_uint(self.slot, self.offset.into(), value) };
+    }
+}
+
+impl<const B: usize, const L: usize> StorageType for TransientUint<B, L> {
+    type Wraps<'a> = Uint<B, L>;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self>;
+
+    const SLOT_BYTES: usize = 0;
+    const TRANSIENT_SLOT_BYTES: usize = (B / 8);
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(B <= 256);
+        Self { slot, offset }
+    }
+
+    unsafe fn new_with_transient(_: U256, _: u8, slot: U256, offset: u8) -> Self {
+        Self::new(slot, offset)
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        self.get()
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<const B: usize, const L: usize> TransientStorageType for TransientUint<B, L> {}
+
+impl<'a, const B: usize, const L: usize> SimpleStorageType<'a> for TransientUint<B, L> {
+    fn set_by_wrapped(&mut self, value: Self::Wraps<'a>) {
+        self.set(value);
+    }
+}
+
+impl<const B: usize, const L: usize> Erase for TransientUint<B, L> {
+    fn erase(&mut self) {
+        self.set(Self::Wraps::ZERO);
+    }
+}
+
+impl<const B: usize, const L: usize> From<TransientUint<B, L>> for Uint<B, L> {
+    fn from(value: TransientUint<B, L>) -> Self {
+        value.get()
+    }
+}
+
+/// Accessor for a transient [`bool`].
+#[derive(Debug)]
+pub struct TransientBool {
+    slot: U256,
+    offset: u8,
+}
+
+impl TransientBool {
+    /// Gets the underlying [`bool`] in transient storage.
+    pub fn get(&self) -> bool {
+        let data = unsafe { TransientStorage::get_byte(self.slot, self.offset.into()) };
+        data != 0
+    }
+
+    /// Sets the underlying [`bool`] in transient storage.
+    pub fn set(&mut self, value: bool) {
+        unsafe { TransientStorage::set_byte(self.slot, self.offset.into(), value as u8) }
+    }
+}
+
+impl StorageType for TransientBool {
+    type Wraps<'a> = bool;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self>;
+
+    const SLOT_BYTES: usize = 0;
+    const TRANSIENT_SLOT_BYTES: usize = 1;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        Self { slot, offset }
+    }
+
+    unsafe fn new_with_transient(_: U256, _: u8, slot: U256, offset: u8) -> Self {
+        Self::new(slot, offset)
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        self.get()
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl TransientStorageType for TransientBool {}
+
+impl<'a> SimpleStorageType<'a> for TransientBool {
+    fn set_by_wrapped(&mut self, value: Self::Wraps<'a>) {
+        self.set(value);
+    }
+}
+
+impl Erase for TransientBool {
+    fn erase(&mut self) {
+        self.set(false);
+    }
+}
+
+impl From<TransientBool> for bool {
+    fn from(value: TransientBool) -> Self {
+        value.get()
+    }
+}
+
+/// Accessor for a transient [`Address`].
+#[derive(Debug)]
+pub struct TransientAddress {
+    slot: U256,
+    offset: u8,
+}
+
+impl TransientAddress {
+    /// Gets the underlying [`Address`] in transient storage.
+    pub fn get(&self) -> Address {
+        unsafe { TransientStorage::get::<20>(self.slot, self.offset.into()).into() }
+    }
+
+    /// Sets the underlying [`Address`] in transient storage.
+    pub fn set(&mut self, value: Address) {
+        unsafe { TransientStorage::set::<20>(self.slot, self.offset.into(), value.into()) }
+    }
+}
+
+impl StorageType for TransientAddress {
+    type Wraps<'a> = Address;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self>;
+
+    const SLOT_BYTES: usize = 0;
+    const TRANSIENT_SLOT_BYTES: usize = 20;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        Self { slot, offset }
+    }
+
+    unsafe fn new_with_transient(_: U256, _: u8, slot: U256, offset: u8) -> Self {
+        Self::new(slot, offset)
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        self.get()
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl TransientStorageType for TransientAddress {}
+
+impl<'a> SimpleStorageType<'a> for TransientAddress {
+    fn set_by_wrapped(&mut self, value: Self::Wraps<'a>) {
+        self.set(value);
+    }
+}
+
+impl Erase for TransientAddress {
+    fn erase(&mut self) {
+        self.set(Self::Wraps::ZERO);
+    }
+}
+
+impl From<TransientAddress> for Address {
+    fn from(value: TransientAddress) -> Self {
+        value.get()
+    }
+}
+
+/// Accessor for a transient map, whose slots are assigned just like those of a [`StorageMap`].
+pub struct TransientMap<K: StorageKey, V: TransientStorageType> {
+    inner: StorageMap<K, V>,
+}
+
+impl<K, V> StorageType for TransientMap<K, V>
+where
+    K: StorageKey,
+    V: TransientStorageType,
+{
+    type Wraps<'a> = StorageGuard<'a, TransientMap<K, V>> where Self: 'a;
+    type WrapsMut<'a> = StorageGuardMut<'a, TransientMap<K, V>> where Self: 'a;
+
+    const SLOT_BYTES: usize = 0;
+    const TRANSIENT_SLOT_BYTES: usize = 32;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(offset == 0);
+        Self {
+            inner: StorageMap::new_transient(slot),
+        }
+    }
+
+    unsafe fn new_with_transient(_: U256, _: u8, slot: U256, offset: u8) -> Self {
+        Self::new(slot, offset)
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<K, V> TransientStorageType for TransientMap<K, V>
+where
+    K: StorageKey,
+    V: TransientStorageType,
+{
+}
+
+impl<K, V> TransientMap<K, V>
+where
+    K: StorageKey,
+    V: TransientStorageType,
+{
+    /// Gets an accessor to the element at the given key, or the zero-value if none is there.
+    /// Note: the accessor is protected by a [`StorageGuard`], which restricts its lifetime
+    /// to that of `&self`.
+    pub fn getter(&self, key: K) -> StorageGuard<'_, V> {
+        self.inner.getter(key)
+    }
+
+    /// Gets a mutable accessor to the element at the given key, or the zero-value is none is there.
+    /// Note: the accessor is protected by a [`StorageGuardMut`], which restricts its lifetime
+    /// to that of `&mut self`.
+    pub fn setter(&mut self, key: K) -> StorageGuardMut<'_, V> {
+        self.inner.setter(key)
+    }
+
+    /// Gets the element at the given key, or the zero value if none is there.
+    pub fn get(&self, key: K) -> V::Wraps<'_> {
+        self.inner.get(key)
+    }
+}
+
+impl<'a, K, V> TransientMap<K, V>
+where
+    K: StorageKey,
+    V: TransientStorageType + SimpleStorageType<'a>,
+{
+    /// Sets the element at a given key, overwriting what may have been there.
+    pub fn insert(&mut self, key: K, value: V::Wraps<'a>) {
+        self.inner.insert(key, value)
+    }
+
+    /// Replace the element at the given key.
+    /// Returns the old element, or the zero-value if none was there.
+    pub fn replace(&mut self, key: K, value: V::Wraps<'a>) -> V::Wraps<'a> {
+        self.inner.replace(key, value)
+    }
+
+    /// Remove the element at the given key.
+    /// Returns the element, or the zero-value if none was there.
+    pub fn take(&mut self, key: K) -> V::Wraps<'a> {
+        self.inner.take(key)
+    }
+}
+
+impl<K, V> TransientMap<K, V>
+where
+    K: StorageKey,
+    V: TransientStorageType + Erase,
+{
+    /// Delete the element at the given key, if it exists.
+    pub fn delete(&mut self, key: K) {
+        self.inner.delete(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransientAddress, TransientBool, TransientMap, TransientU256, TransientU8};
+    use crate::{crypto, prelude::*, storage::StorageAddress, storage::StorageU256};
+    use alloy_primitives::{Address, U256};
+
+    #[storage]
+    struct Lock {
+        count: TransientU256,
+        locked: TransientBool,
+        owner: TransientAddress,
+        nonces: TransientMap<Address, TransientU256>,
+    }
+
+    #[storage]
+    struct Vault {
+        locked: TransientBool,
+        balance: StorageU256,
+        flags: TransientU8,
+        owner: StorageAddress,
+        #[borrow]
+        lock: Lock,
+        admin: StorageAddress,
+    }
+
+    #[storage]
+    struct PlainVault {
+        balance: StorageU256,
+        owner: StorageAddress,
+        admin: StorageAddress,
+    }
+
+    #[test]
+    fn test_transient_layout() {
+        let lock = unsafe { Lock::new(U256::from(7), 0) };
+        assert_eq!((lock.count.slot, lock.count.offset), (U256::from(7), 0));
+        assert_eq!((lock.locked.slot, lock.locked.offset), (U256::from(8), 31));
+        assert_eq!((lock.owner.slot, lock.owner.offset), (U256::from(8), 11));
+        assert_eq!(<Lock as StorageType>::REQUIRED_SLOTS, 0);
+        assert_eq!(<Lock as StorageType>::TRANSIENT_REQUIRED_SLOTS, 3);
+
+        let mut key = [0; 64];
+        key[12..32].copy_from_slice(&[1; 20]);
+        key[32..].copy_from_slice(&U256::from(9).to_be_bytes::<32>());
+        let slot: U256 = crypto::keccak(key).into();
+        let nonce = lock.nonces.getter(Address::repeat_byte(1));
+        assert_eq!((nonce.slot, nonce.offset), (slot, 0));
+    }
+
+    #[test]
+    fn test_transient_fields_keep_persistent_slots() {
+        let vault = unsafe { Vault::new(U256::ZERO, 0) };
+        let plain = unsafe { PlainVault::new(U256::ZERO, 0) };
+        assert_eq!(vault.balance.slot, plain.balance.slot);
+        assert_eq!(vault.owner.slot, plain.owner.slot);
+        assert_eq!(vault.owner.offset, plain.owner.offset);
+        assert_eq!(vault.admin.slot, plain.admin.slot);
+        assert_eq!(vault.admin.offset, plain.admin.offset);
+        assert_eq!(
+            <Vault as StorageType>::REQUIRED_SLOTS,
+            <PlainVault as StorageType>::REQUIRED_SLOTS,
+        );
+
+        // transient fields, including those of nested structs, have a layout of their own
+        assert_eq!((vault.locked.slot, vault.locked.offset), (U256::ZERO, 31));
+        assert_eq!((vault.flags.slot, vault.flags.offset), (U256::ZERO, 30));
+        let (count, owner) = (&vault.lock.count, &vault.lock.owner);
+        assert_eq!((count.slot, count.offset), (U256::from(1), 0));
+        assert_eq!((owner.slot, owner.offset), (U256::from(2), 11));
+        assert_eq!(<Vault as StorageType>::TRANSIENT_REQUIRED_SLOTS, 4);
+    }
+
+    #[test]
+    fn test_transient_load_store() {
+        let mut vault = unsafe { Vault::new(U256::ZERO, 0) };
+        assert!(!vault.locked.get());
+        assert_eq!(vault.lock.count.get(), U256::ZERO);
+
+        vault.locked.set(true);
+        vault.flags.set(alloy_primitives::aliases::U8::from(0xab));
+        vault.lock.count.set(U256::from(5));
+        vault.lock.owner.set(Address::repeat_byte(2));
+        vault.lock.nonces.insert(Address::repeat_byte(3), U256::from(9));
+
+        // values packed into the same word don't clobber each other
+        let alias = unsafe { Vault::new(U256::ZERO, 0) };
+        assert!(alias.locked.get());
+        assert_eq!(alias.flags.get(), alloy_primitives::aliases::U8::from(0xab));
+        assert_eq!(alias.lock.count.get(), U256::from(5));
+        assert!(!alias.lock.locked.get());
+        assert_eq!(alias.lock.owner.get(), Address::repeat_byte(2));
+        assert_eq!(alias.lock.nonces.get(Address::repeat_byte(3)), U256::from(9));
+        assert_eq!(alias.lock.nonces.get(Address::repeat_byte(4)), U256::ZERO);
+
+        vault.locked.set(false);
+        assert_eq!(vault.lock.nonces.take(Address::repeat_byte(3)), U256::from(9));
+        assert!(!alias.locked.get());
+        assert_eq!(alias.flags.get(), alloy_primitives::aliases::U8::from(0xab));
+        assert_eq!(alias.lock.nonces.get(Address::repeat_byte(3)), U256::ZERO);
+    }
+}
@@ -21,6 +21,8 @@ impl<S: StorageType> StorageType for StorageVec<S> {
 
     unsafe fn new(slot: U256, offset: u8) -> Self {
         debug_assert!(offset == 0);
+        let () = Self::NOT_NAMESPACED;
+        let () = Self::NOT_TRANSIENT;
         Self {
             slot,
             base: OnceCell::new(),
@@ -38,6 +40,9 @@ impl<S: StorageType> StorageType for StorageVec<S> {
 }
 
 impl<S: StorageType> StorageVec<S> {
+    const NOT_NAMESPACED: () = super::assert_not_namespaced::<S>();
+    const NOT_TRANSIENT: () = super::assert_not_transient::<S>();
+
     /// Returns `true` if the collection contains no elements.
     pub fn is_empty(&self) -> bool {
         self.len() == 0